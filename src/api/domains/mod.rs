@@ -1,9 +1,11 @@
 use crate::api::rules::Rule;
 use crate::api::ResponseStatus;
 use crate::client::Mailinator;
-use crate::path::AsUrl;
+use crate::names::{DomainRef, NameInput};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
 use async_trait::async_trait;
-use eyre::Report;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -22,16 +24,24 @@ pub struct DomainResponse {
 }
 
 #[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct DomainRequest {
-    domain: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainRef,
 }
 
 impl AsUrl for DomainRequest {
-    fn as_url_path(self) -> String {
-        format!(
-            "/api/v2/domains/{domain_id}",
-            domain_id = self.domain
-        )
+    fn as_url_path(self) -> Result<String, Error> {
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(self.domain)
+            .build())
     }
 }
 
@@ -39,45 +49,50 @@ impl AsUrl for DomainRequest {
 pub trait ApiDomainEndpoints {
     async fn get_all_domains(
         &self,
-    ) -> Result<DomainResponse, Report>;
+    ) -> Result<DomainResponse, Error>;
     async fn get_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<Domain, Report>;
+    ) -> Result<Domain, Error>;
     async fn create_private_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<ResponseStatus, Report>;
+    ) -> Result<ResponseStatus, Error>;
     async fn delete_private_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<ResponseStatus, Report>;
+    ) -> Result<ResponseStatus, Error>;
 }
 
 #[async_trait]
 impl ApiDomainEndpoints for Mailinator {
     async fn get_all_domains(
         &self,
-    ) -> Result<DomainResponse, Report> {
-        let path = "/domains/";
-        self.get(path.to_owned()).await
+    ) -> Result<DomainResponse, Error> {
+        self.get(
+            ApiPath::v2()
+                .segment("domains")
+                .trailing_slash()
+                .build(),
+        )
+        .await
     }
     async fn get_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<Domain, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<Domain, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn create_private_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<ResponseStatus, Report> {
-        self.post(request.as_url_path(), vec![]).await
+    ) -> Result<ResponseStatus, Error> {
+        self.post(request.as_url_path()?, vec![]).await
     }
     async fn delete_private_domain(
         &self,
         request: DomainRequest,
-    ) -> Result<ResponseStatus, Report> {
-        self.delete(request.as_url_path()).await
+    ) -> Result<ResponseStatus, Error> {
+        self.delete(request.as_url_path()?).await
     }
 }
@@ -0,0 +1,233 @@
+use super::{
+    inbox::Msg,
+    mime::{decode_encoded_words, split_unquoted, unquote},
+    Email,
+};
+use std::fmt;
+
+/// A parsed email address with its optional display name.
+///
+/// ```rust
+/// use mailinator_rs::prelude::Mailbox;
+///
+/// let mailbox =
+///     Mailbox::parse(r#""=?utf-8?Q?Caf=C3=A9?= Team" <NoReply+signup@OurApp.com>"#)
+///         .unwrap();
+/// assert_eq!(mailbox.display_name.as_deref(), Some("Café Team"));
+/// assert_eq!(mailbox.local_part, "NoReply+signup");
+/// assert_eq!(mailbox.domain, "ourapp.com");
+/// assert_eq!(mailbox.tag(), Some("signup"));
+/// assert!(mailbox.matches("noreply@ourapp.com"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mailbox {
+    /// The decoded display name, if any.
+    pub display_name: Option<String>,
+    /// The part before `@`, unquoted but otherwise as sent.
+    pub local_part: String,
+    /// The lower cased part after `@`.
+    pub domain: String,
+}
+
+impl Mailbox {
+    /// Parses `Name <local@domain>`, `local@domain (Name)` or a
+    /// bare address. Returns `None` when no address is found.
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (name, addr) = match split_angle(input) {
+            Some((name, addr)) => (name, addr),
+            None => match input.split_once('(') {
+                Some((addr, comment)) => (
+                    comment
+                        .trim_end()
+                        .trim_end_matches(')'),
+                    addr,
+                ),
+                None => ("", input),
+            },
+        };
+        let (local, domain) =
+            addr.trim().rsplit_once('@')?;
+        let local_part = unquote(local.trim());
+        let domain = domain.trim().to_ascii_lowercase();
+        if local_part.is_empty()
+            || domain.is_empty()
+            || domain.contains(char::is_whitespace)
+        {
+            return None;
+        }
+        let display_name =
+            decode_encoded_words(&unquote(name.trim()))
+                .trim()
+                .to_owned();
+        Some(Self {
+            display_name: (!display_name.is_empty())
+                .then_some(display_name),
+            local_part,
+            domain,
+        })
+    }
+
+    /// Parses a comma separated address list, skipping entries
+    /// without an address.
+    #[must_use]
+    pub fn parse_list(input: &str) -> Vec<Self> {
+        split_unquoted(input, ',')
+            .into_iter()
+            .filter_map(Self::parse)
+            .collect()
+    }
+
+    /// `local_part@domain`.
+    #[must_use]
+    pub fn address(&self) -> String {
+        format!("{}@{}", self.local_part, self.domain)
+    }
+
+    /// The plus-addressing tag, e.g. `signup` in
+    /// `noreply+signup@ourapp.com`.
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.local_part.split_once('+').map(|(_, tag)| tag)
+    }
+
+    /// The local part without its plus-addressing tag.
+    #[must_use]
+    pub fn base_local_part(&self) -> &str {
+        self.local_part.split_once('+').map_or(
+            self.local_part.as_str(),
+            |(base, _)| base,
+        )
+    }
+
+    /// Lower cased address without its plus-addressing tag,
+    /// suitable for grouping messages by sender.
+    #[must_use]
+    pub fn normalized(&self) -> String {
+        format!(
+            "{}@{}",
+            self.base_local_part().to_lowercase(),
+            self.domain
+        )
+    }
+
+    /// Whether `address` designates this mailbox, ignoring case
+    /// and plus-addressing tags.
+    #[must_use]
+    pub fn matches(&self, address: &str) -> bool {
+        Self::parse(address).is_some_and(|other| {
+            other.normalized() == self.normalized()
+        })
+    }
+}
+
+impl fmt::Display for Mailbox {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match &self.display_name {
+            Some(name) => write!(
+                f,
+                "\"{}\" <{}>",
+                name.replace('\\', "\\\\")
+                    .replace('"', "\\\""),
+                self.address()
+            ),
+            None => f.write_str(&self.address()),
+        }
+    }
+}
+
+/// Splits `Name <addr>` at the angle brackets outside of quotes.
+fn split_angle(input: &str) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (idx, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '<' if !quoted => {
+                let rest = &input[idx + 1..];
+                let end = rest.find('>')?;
+                return Some((&input[..idx], &rest[..end]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Completes a bare inbox name with the message domain.
+fn with_domain(
+    to: &str,
+    domain: Option<&str>,
+) -> Option<Mailbox> {
+    Mailbox::parse(to).or_else(|| {
+        let to = to.trim();
+        (!to.is_empty())
+            .then(|| {
+                Mailbox::parse(&format!("{to}@{}", domain?))
+            })
+            .flatten()
+    })
+}
+
+impl Msg {
+    /// The parsed sender, when `from` holds an address.
+    #[must_use]
+    pub fn sender(&self) -> Option<Mailbox> {
+        Mailbox::parse(self.from.as_deref()?)
+    }
+
+    /// The parsed recipient, completing a bare inbox name with the
+    /// message domain.
+    #[must_use]
+    pub fn recipient(&self) -> Option<Mailbox> {
+        with_domain(
+            self.to.as_deref()?,
+            self.domain.as_deref(),
+        )
+    }
+}
+
+impl Email {
+    /// The parsed sender, read from the `From` header, then from
+    /// `fromfull` with `from` as display name.
+    #[must_use]
+    pub fn sender(&self) -> Option<Mailbox> {
+        self.header("from")
+            .and_then(Mailbox::parse)
+            .or_else(|| {
+                let mut mailbox = Mailbox::parse(
+                    self.fromfull.as_deref()?,
+                )?;
+                if mailbox.display_name.is_none() {
+                    mailbox.display_name = self
+                        .from
+                        .as_deref()
+                        .map(decode_encoded_words)
+                        .filter(|n| {
+                            !n.is_empty()
+                                && !n.contains('@')
+                        });
+                }
+                Some(mailbox)
+            })
+            .or_else(|| {
+                Mailbox::parse(self.from.as_deref()?)
+            })
+    }
+
+    /// The parsed recipients, read from the `To` header, then from
+    /// `to`.
+    #[must_use]
+    pub fn recipients(&self) -> Vec<Mailbox> {
+        self.header("to")
+            .or(self.to.as_deref())
+            .map(Mailbox::parse_list)
+            .unwrap_or_default()
+    }
+}
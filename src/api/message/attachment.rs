@@ -1,21 +1,67 @@
-use crate::path::AsUrl;
+use super::{mime::ContentType, ApiMessageEndpoints};
+use crate::client::Mailinator;
+use crate::names::{
+    DomainName, InboxName, MessageId, NameInput,
+};
+use crate::path::{ApiPath, AsUrl};
+use crate::Error;
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    Response,
+};
 use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchListOfAttachmentRequest {
-    domain: String,
-    inbox: String,
-    message_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
 }
 
 impl AsUrl for FetchListOfAttachmentRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self {
             domain,
             inbox,
             message_id,
         } = self;
-        format!("/api/v2/domains/{domain}/inboxes/{inbox}/messages/{message_id}/attachments")
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(inbox)
+            .segment("messages")
+            .segment(message_id)
+            .segment("attachments")
+            .build())
     }
 }
 
@@ -49,25 +95,488 @@ impl Default for LookupField {
     }
 }
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchAttachmentRequest {
-    domain: String,
-    inbox: String,
-    message_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
     attachment: LookupField,
 }
 
 impl AsUrl for FetchAttachmentRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self {
             domain,
             inbox,
             message_id,
             attachment,
         } = self;
-        match attachment {
-            LookupField::Id(id) => format!("/api/v2/domains/{domain}/inboxes/{inbox}/messages/{message_id}/attachments/{id}"),
-            LookupField::Name(name) => format!("/api/v2/domains/{domain}/inboxes/{inbox}/messages/{message_id}/attachments/{name}")
+        let path = ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(inbox)
+            .segment("messages")
+            .segment(message_id)
+            .segment("attachments");
+        Ok(match attachment {
+            LookupField::Id(id) => {
+                path.segment(id.to_string())
+            }
+            LookupField::Name(name) => path.segment(name),
         }
+        .build())
     }
 }
+
+/// Metadata of a downloaded attachment, read from the response
+/// headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentInfo {
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    /// Number of bytes downloaded.
+    pub size: u64,
+}
+
+/// An attachment downloaded in memory.
+#[derive(Debug, Clone)]
+pub struct AttachmentFile {
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl AttachmentFile {
+    /// Writes the attachment content to `path`, replacing any
+    /// existing file.
+    ///
+    /// # Errors
+    /// Returns [`Error::Io`] when the file can not be written.
+    pub async fn save_to(
+        &self,
+        path: impl AsRef<Path> + Send,
+    ) -> Result<(), Error> {
+        tokio::fs::write(path, &self.data).await?;
+        Ok(())
+    }
+}
+
+impl Mailinator {
+    /// Requests an attachment and reads its metadata, leaving the
+    /// body to be consumed.
+    pub(crate) async fn open_attachment(
+        &self,
+        request: FetchAttachmentRequest,
+    ) -> Result<(AttachmentInfo, Response), Error> {
+        let requested_name = match &request.attachment {
+            LookupField::Name(name) => Some(name.clone()),
+            LookupField::Id(_) => None,
+        };
+        let resp = self
+            .get_response(request.as_url_path()?)
+            .await?;
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned)
+        };
+        let filename = header(CONTENT_DISPOSITION)
+            .and_then(|d| {
+                ContentType::parse(&d)
+                    .param_decoded("filename")
+            })
+            .or(requested_name);
+        let info = AttachmentInfo {
+            filename,
+            content_type: header(CONTENT_TYPE),
+            size: 0,
+        };
+        Ok((info, resp))
+    }
+
+    pub(crate) async fn download_attachment_into(
+        &self,
+        request: FetchAttachmentRequest,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<AttachmentInfo, Error> {
+        let (mut info, mut resp) =
+            self.open_attachment(request).await?;
+        while let Some(chunk) = resp.chunk().await? {
+            writer.write_all(&chunk).await?;
+            info.size += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(info)
+    }
+
+    pub(crate) async fn download_attachment_bytes(
+        &self,
+        request: FetchAttachmentRequest,
+    ) -> Result<AttachmentFile, Error> {
+        let (info, resp) =
+            self.open_attachment(request).await?;
+        Ok(AttachmentFile {
+            filename: info.filename,
+            content_type: info.content_type,
+            data: resp.bytes().await?.to_vec(),
+        })
+    }
+}
+
+/// Downloads every attachment of a message.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct FetchAllAttachmentsRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
+    /// Maximum number of downloads in flight.
+    #[builder(default = "4")]
+    concurrency: usize,
+    /// Directory the attachments are also written into, with safe
+    /// and unique file names. It is created when missing.
+    #[builder(default)]
+    directory: Option<PathBuf>,
+}
+
+/// Identifies a downloaded attachment.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum AttachmentKey {
+    /// The id given by the attachment listing.
+    Id(u64),
+    /// Position in the listing of an attachment listed without an
+    /// id.
+    Position(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadedAttachment {
+    pub key: AttachmentKey,
+    pub file: AttachmentFile,
+    /// Where the attachment was written, if a directory was given.
+    pub path: Option<PathBuf>,
+}
+
+/// Attachments of a message keyed by [`AttachmentKey`].
+#[derive(Debug, Clone, Default)]
+pub struct DownloadedAttachments {
+    by_id: BTreeMap<AttachmentKey, DownloadedAttachment>,
+}
+
+impl DownloadedAttachments {
+    /// The attachment listed with `attachment_id`.
+    #[must_use]
+    pub fn get(
+        &self,
+        attachment_id: u64,
+    ) -> Option<&DownloadedAttachment> {
+        self.by_id.get(&AttachmentKey::Id(attachment_id))
+    }
+
+    #[must_use]
+    pub fn get_key(
+        &self,
+        key: AttachmentKey,
+    ) -> Option<&DownloadedAttachment> {
+        self.by_id.get(&key)
+    }
+
+    /// The first attachment named `filename`.
+    #[must_use]
+    pub fn by_filename(
+        &self,
+        filename: &str,
+    ) -> Option<&DownloadedAttachment> {
+        self.by_id.values().find(|a| {
+            a.file.filename.as_deref() == Some(filename)
+        })
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = &DownloadedAttachment> {
+        self.by_id.values()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    #[must_use]
+    pub fn into_map(
+        self,
+    ) -> BTreeMap<AttachmentKey, DownloadedAttachment> {
+        self.by_id
+    }
+}
+
+impl Mailinator {
+    /// Lists the attachments of a message and downloads all of them
+    /// concurrently.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchAllAttachmentsRequestBuilder, Mailinator};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchAllAttachmentsRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(String::from("testinbox"))
+    ///        .message_id(String::from("msg_id"))
+    ///        .directory(Some("target/attachments".into()))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.fetch_all_attachments(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(files) => println!("{} attachments", files.len()),
+    ///    }
+    ///}
+    ///```
+    ///
+    /// Attachments listed without an id are fetched by filename;
+    /// those with neither are skipped.
+    ///
+    /// # Errors
+    /// Returns the first error raised while listing, downloading or
+    /// writing an attachment.
+    pub async fn fetch_all_attachments(
+        &self,
+        request: FetchAllAttachmentsRequest,
+    ) -> Result<DownloadedAttachments, Error> {
+        let FetchAllAttachmentsRequest {
+            domain,
+            inbox,
+            message_id,
+            concurrency,
+            directory,
+        } = request;
+        let listing = self
+            .fetch_list_of_attachments(
+                FetchListOfAttachmentRequest {
+                    domain: domain.clone(),
+                    inbox: inbox.clone(),
+                    message_id: message_id.clone(),
+                },
+            )
+            .await?;
+
+        let mut files: Vec<(
+            AttachmentKey,
+            AttachmentFile,
+        )> = stream::iter(
+            listing
+                .attachments
+                .into_iter()
+                .enumerate()
+                .filter_map(|(idx, listed)| {
+                    let (key, attachment) =
+                        lookup(idx, &listed)?;
+                    Some((key, attachment, listed))
+                }),
+        )
+        .map(|(key, attachment, listed)| {
+            let request = FetchAttachmentRequest {
+                domain: domain.clone(),
+                inbox: inbox.clone(),
+                message_id: message_id.clone(),
+                attachment,
+            };
+            async move {
+                let mut file = self
+                    .download_attachment_bytes(request)
+                    .await?;
+                if listed.filename.is_some() {
+                    file.filename = listed.filename;
+                }
+                file.content_type = file
+                    .content_type
+                    .or(listed.content_type);
+                Ok::<_, Error>((key, file))
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await?;
+
+        // Name clashes resolve in listing order.
+        files.sort_by_key(|(key, _)| *key);
+        let mut downloaded =
+            DownloadedAttachments::default();
+        let mut used_names = HashSet::new();
+        if let Some(dir) = &directory {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        for (key, file) in files {
+            let path = match &directory {
+                Some(dir) => {
+                    let path = unique_path(
+                        dir,
+                        &safe_filename(
+                            file.filename.as_deref(),
+                            key,
+                        ),
+                        &mut used_names,
+                    )
+                    .await?;
+                    file.save_to(&path).await?;
+                    Some(path)
+                }
+                None => None,
+            };
+            downloaded.by_id.insert(
+                key,
+                DownloadedAttachment { key, file, path },
+            );
+        }
+        Ok(downloaded)
+    }
+}
+
+/// How to key and fetch the attachment listed at `idx`: by id,
+/// else by filename, else not at all.
+fn lookup(
+    idx: usize,
+    listed: &Attachment,
+) -> Option<(AttachmentKey, LookupField)> {
+    match (listed.attachment_id, listed.filename.as_deref())
+    {
+        (Some(id), _) => Some((
+            AttachmentKey::Id(id),
+            LookupField::Id(id),
+        )),
+        (None, Some(name)) if !name.is_empty() => Some((
+            AttachmentKey::Position(idx),
+            LookupField::Name(name.to_owned()),
+        )),
+        _ => {
+            tracing::warn!(
+                position = idx,
+                "skipping attachment listed without id or filename"
+            );
+            None
+        }
+    }
+}
+
+/// Keeps the last path component of `name`, drops control
+/// characters and replaces those unsafe on common file systems.
+fn safe_filename(
+    name: Option<&str>,
+    key: AttachmentKey,
+) -> String {
+    let base = name
+        .and_then(|n| n.rsplit(['/', '\\']).next())
+        .unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| match c {
+            ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c: char| {
+        c == '.' || c.is_whitespace()
+    });
+    if cleaned.is_empty() {
+        match key {
+            AttachmentKey::Id(id) => {
+                format!("attachment-{id}")
+            }
+            AttachmentKey::Position(idx) => {
+                format!("attachment-at-{idx}")
+            }
+        }
+    } else {
+        cleaned.to_owned()
+    }
+}
+
+/// Appends ` (n)` before the extension until the name is neither
+/// used by this download nor present on disk.
+async fn unique_path(
+    dir: &Path,
+    filename: &str,
+    used: &mut HashSet<String>,
+) -> Result<PathBuf, Error> {
+    let (stem, ext) = match filename.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => {
+            (stem, Some(ext))
+        }
+        _ => (filename, None),
+    };
+    let mut candidate = filename.to_owned();
+    let mut n = 1;
+    while used.contains(&candidate)
+        || tokio::fs::try_exists(dir.join(&candidate))
+            .await?
+    {
+        candidate = ext.map_or_else(
+            || format!("{stem} ({n})"),
+            |ext| format!("{stem} ({n}).{ext}"),
+        );
+        n += 1;
+    }
+    let path = dir.join(&candidate);
+    used.insert(candidate);
+    Ok(path)
+}
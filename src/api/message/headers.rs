@@ -0,0 +1,243 @@
+use super::mime::decode_encoded_words;
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Value;
+use std::{collections::HashMap, fmt};
+
+/// Message headers in their original order, looked up ignoring
+/// case.
+///
+/// Headers sent several times, e.g. `Received`, keep every value.
+/// Values are stored as sent; the `decoded` accessors revert RFC
+/// 2047 encoded words.
+///
+/// ```rust
+/// use mailinator_rs::prelude::Headers;
+///
+/// let headers: Headers = [
+///     ("Subject", "=?UTF-8?B?SMOpbGxv?= world"),
+///     ("Received", "from a"),
+///     ("received", "from b"),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert_eq!(headers.get_decoded("subject").as_deref(), Some("Héllo world"));
+/// assert_eq!(headers.get_all("RECEIVED").count(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers(Vec<(String, String)>);
+
+impl Headers {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// The first value of a header, as sent.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// Every value of a header, in order.
+    pub fn get_all<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let name = name.to_owned();
+        self.0
+            .iter()
+            .filter(move |(n, _)| {
+                n.eq_ignore_ascii_case(&name)
+            })
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The first value of a header with encoded words decoded.
+    #[must_use]
+    pub fn get_decoded(
+        &self,
+        name: &str,
+    ) -> Option<String> {
+        self.get(name).map(decode_encoded_words)
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Adds a value, keeping the existing ones.
+    pub fn append(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.0.push((name.into(), value.into()));
+    }
+
+    /// Replaces every value of a header.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        let name = name.into();
+        self.remove(&name);
+        self.0.push((name, value.into()));
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.0
+            .retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+
+    /// Every header with encoded words decoded.
+    #[must_use]
+    pub fn decoded(&self) -> Self {
+        self.0
+            .iter()
+            .map(|(n, v)| {
+                (n.clone(), decode_encoded_words(v))
+            })
+            .collect()
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)>
+    for Headers
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(n, v)| (n.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl From<HashMap<String, String>> for Headers {
+    fn from(map: HashMap<String, String>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl IntoIterator for Headers {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Serialized as an object, headers with several values becoming
+/// arrays, as Mailinator sends them.
+impl Serialize for Headers {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in &self.0 {
+            if !names
+                .iter()
+                .any(|n| n.eq_ignore_ascii_case(name))
+            {
+                names.push(name);
+            }
+        }
+        let mut map =
+            serializer.serialize_map(Some(names.len()))?;
+        for name in names {
+            let values: Vec<&str> =
+                self.get_all(name).collect();
+            if let [value] = values.as_slice() {
+                map.serialize_entry(name, value)?;
+            } else {
+                map.serialize_entry(name, &values)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Headers {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct HeadersVisitor;
+
+        impl<'de> Visitor<'de> for HeadersVisitor {
+            type Value = Headers;
+
+            fn expecting(
+                &self,
+                f: &mut fmt::Formatter<'_>,
+            ) -> fmt::Result {
+                f.write_str(
+                    "a map of header names to values",
+                )
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<Headers, A::Error> {
+                let mut headers = Headers::new();
+                while let Some((name, value)) =
+                    access.next_entry::<String, Value>()?
+                {
+                    match value {
+                        Value::Array(values) => {
+                            for value in values {
+                                headers.append(
+                                    name.clone(),
+                                    to_text(value),
+                                );
+                            }
+                        }
+                        value => {
+                            headers.append(
+                                name,
+                                to_text(value),
+                            );
+                        }
+                    }
+                }
+                Ok(headers)
+            }
+        }
+
+        deserializer.deserialize_map(HeadersVisitor)
+    }
+}
+
+fn to_text(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -1,6 +1,12 @@
-use crate::path::AsUrl;
+use super::mime::decode_encoded_words;
+use crate::names::{DomainName, InboxName, NameInput};
+use crate::path::{encode_segment, ApiPath, AsUrl};
+use crate::Error;
+
+use chrono::{DateTime, Utc};
+use derive_builder::UninitializedFieldError;
 use serde::{Deserialize, Serialize};
-use serde_qs as qs;
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Clone)]
 pub enum Sorting {
@@ -11,39 +17,270 @@ pub enum Sorting {
 }
 
 #[derive(Debug, Builder, Default, Serialize, Clone)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchInboxRequestQueryParams {
+    #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     skip: Option<usize>,
+    #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     limit: Option<usize>,
+    #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     sort: Option<Sorting>,
+    #[builder(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     decode_subject: Option<bool>,
 }
 
-#[derive(Debug, Builder, Default)]
+/// The inboxes of a domain a request applies to.
+///
+/// Parsing a string follows the api syntax: `*` selects every
+/// inbox, a trailing `*` a prefix and commas separate names. Each
+/// name is validated as an [`InboxName`].
+///
+/// ```rust
+/// use mailinator_rs::prelude::{InboxName, InboxSelector};
+///
+/// let qa = InboxName::try_from("qa-").unwrap();
+/// assert_eq!(InboxSelector::try_from("qa-*").unwrap(), InboxSelector::Prefix(qa));
+/// assert_eq!(
+///     InboxSelector::try_from("alice,bob").unwrap().to_path_segment(),
+///     "alice,bob"
+/// );
+/// assert!(InboxSelector::try_from("qa@example.com").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InboxSelector {
+    Single(InboxName),
+    List(Vec<InboxName>),
+    /// Every inbox whose name starts with the prefix.
+    Prefix(InboxName),
+    /// Every inbox of the domain.
+    #[default]
+    All,
+}
+
+impl InboxSelector {
+    /// The selector as a path segment, each name percent-encoded
+    /// and the `,` and `*` separators kept as is.
+    #[must_use]
+    pub fn to_path_segment(&self) -> String {
+        match self {
+            Self::Single(name) => {
+                encode_segment(name.as_str())
+            }
+            Self::List(names) => names
+                .iter()
+                .map(|n| encode_segment(n.as_str()))
+                .collect::<Vec<_>>()
+                .join(","),
+            Self::Prefix(prefix) => {
+                format!(
+                    "{}*",
+                    encode_segment(prefix.as_str())
+                )
+            }
+            Self::All => String::from("*"),
+        }
+    }
+
+    /// The inbox name when a single inbox is selected.
+    #[must_use]
+    pub fn as_single(&self) -> Option<&str> {
+        match self {
+            Self::Single(name) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    fn from_names<S: AsRef<str>>(
+        names: &[S],
+    ) -> Result<Self, Error> {
+        if names.is_empty() {
+            return Err(Error::Builder(String::from(
+                "invalid inbox list: no inbox names",
+            )));
+        }
+        names
+            .iter()
+            .map(|n| InboxName::try_from(n.as_ref()))
+            .collect::<Result<_, _>>()
+            .map(Self::List)
+    }
+}
+
+impl TryFrom<&str> for InboxSelector {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value == "*" {
+            Ok(Self::All)
+        } else if value.contains(',') {
+            Self::from_names(
+                &value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty())
+                    .collect::<Vec<_>>(),
+            )
+        } else if let Some(prefix) = value.strip_suffix('*')
+        {
+            InboxName::try_from(prefix).map(Self::Prefix)
+        } else {
+            InboxName::try_from(value).map(Self::Single)
+        }
+    }
+}
+
+impl TryFrom<String> for InboxSelector {
+    type Error = Error;
+
+    fn try_from(
+        value: String,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl TryFrom<Vec<String>> for InboxSelector {
+    type Error = Error;
+
+    fn try_from(
+        names: Vec<String>,
+    ) -> Result<Self, Self::Error> {
+        Self::from_names(&names)
+    }
+}
+
+impl FromStr for InboxSelector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl From<InboxName> for InboxSelector {
+    fn from(name: InboxName) -> Self {
+        Self::Single(name)
+    }
+}
+
+/// An inbox selector given to a request builder, parsed when the
+/// request is built.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorInput(Option<SelectorValue>);
+
+#[derive(Debug, Clone)]
+enum SelectorValue {
+    Syntax(String),
+    Names(Vec<String>),
+    Selector(InboxSelector),
+}
+
+impl SelectorInput {
+    /// Parses a mandatory field.
+    pub fn required(
+        &self,
+        field: &'static str,
+    ) -> Result<InboxSelector, Error> {
+        self.optional()?.ok_or_else(|| {
+            UninitializedFieldError::new(field).into()
+        })
+    }
+
+    /// Parses a field, `None` when unset.
+    pub fn optional(
+        &self,
+    ) -> Result<Option<InboxSelector>, Error> {
+        self.0
+            .as_ref()
+            .map(|value| match value {
+                SelectorValue::Syntax(s) => {
+                    InboxSelector::try_from(s.as_str())
+                }
+                SelectorValue::Names(names) => {
+                    InboxSelector::from_names(names)
+                }
+                SelectorValue::Selector(selector) => {
+                    Ok(selector.clone())
+                }
+            })
+            .transpose()
+    }
+}
+
+impl From<&str> for SelectorInput {
+    fn from(value: &str) -> Self {
+        Self(Some(SelectorValue::Syntax(value.to_owned())))
+    }
+}
+
+impl From<String> for SelectorInput {
+    fn from(value: String) -> Self {
+        Self(Some(SelectorValue::Syntax(value)))
+    }
+}
+
+impl From<Vec<String>> for SelectorInput {
+    fn from(names: Vec<String>) -> Self {
+        Self(Some(SelectorValue::Names(names)))
+    }
+}
+
+impl From<InboxSelector> for SelectorInput {
+    fn from(selector: InboxSelector) -> Self {
+        Self(Some(SelectorValue::Selector(selector)))
+    }
+}
+
+impl From<InboxName> for SelectorInput {
+    fn from(name: InboxName) -> Self {
+        InboxSelector::Single(name).into()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchInboxRequest {
-    domain: String,
-    inbox: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "SelectorInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxSelector,
     query_params: Option<FetchInboxRequestQueryParams>,
 }
 
 impl AsUrl for FetchInboxRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self {
             domain,
             inbox,
             query_params,
         } = self;
-
-        query_params.map_or_else(|| format!(
-            "/api/v2/domains/{domain}/inboxes/{inbox}"
-            ), |q|{
-            let qsp = qs::to_string(&q).expect(
-                "Failed to serialize query string params",);
-            format!("/api/v2/domains/{domain}/inboxes/{inbox}?{qsp}")
-        })
+        let path = ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .raw_segment(&inbox.to_path_segment());
+        Ok(match query_params {
+            Some(q) => path.query(&q)?,
+            None => path,
+        }
+        .build())
     }
 }
 
@@ -65,9 +302,71 @@ pub struct Msg {
     pub seconds_ago: Option<u64>,
 }
 
+impl Msg {
+    /// The subject with RFC 2047 encoded words decoded, for inboxes
+    /// fetched without `decode_subject`.
+    #[must_use]
+    pub fn decoded_subject(&self) -> Option<String> {
+        self.subject.as_deref().map(decode_encoded_words)
+    }
+
+    /// When Mailinator received the message.
+    #[must_use]
+    pub fn received_at(&self) -> Option<DateTime<Utc>> {
+        from_millis(self.time)
+    }
+
+    /// Whether the message was received at or after `since` and
+    /// before `until`, each bound being optional.
+    #[must_use]
+    pub fn received_within(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> bool {
+        within(self.received_at(), since, until)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct FetchInboxResponse {
     pub domain: String,
     pub to: String,
     pub msgs: Vec<Msg>,
 }
+
+impl FetchInboxResponse {
+    /// Messages received at or after `since` and before `until`.
+    pub fn received_within(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> impl Iterator<Item = &Msg> {
+        self.msgs.iter().filter(move |m| {
+            m.received_within(since, until)
+        })
+    }
+}
+
+/// Converts the epoch milliseconds used by Mailinator.
+pub fn from_millis(
+    time: Option<u64>,
+) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(
+        i64::try_from(time?).ok()?,
+    )
+}
+
+pub fn within(
+    at: Option<DateTime<Utc>>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> bool {
+    if since.is_none() && until.is_none() {
+        return true;
+    }
+    at.is_some_and(|at| {
+        since.map_or(true, |s| at >= s)
+            && until.map_or(true, |u| at < u)
+    })
+}
@@ -0,0 +1,321 @@
+use super::{
+    inbox::{
+        FetchInboxRequestBuilder,
+        FetchInboxRequestQueryParamsBuilder, InboxSelector,
+        Sorting,
+    },
+    ApiMessageEndpoints, Email, FetchMessageRequestBuilder,
+};
+use crate::client::Mailinator;
+use crate::names::{
+    DomainName, InboxName, NameInput, OptionalNameInput,
+};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::{cmp::Reverse, collections::HashSet};
+
+/// Latest messages of a domain, or of a single inbox, with their
+/// full content.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct FetchLatestMessagesRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "OptionalNameInput",
+            build = "self.inbox.optional()?"
+        )
+    )]
+    inbox: Option<InboxName>,
+}
+
+impl AsUrl for FetchLatestMessagesRequest {
+    fn as_url_path(self) -> Result<String, Error> {
+        let Self { domain, inbox } = self;
+        let path = ApiPath::v2()
+            .segment("domains")
+            .segment(domain);
+        let path = match inbox {
+            Some(ibx) => {
+                path.segment("inboxes").segment(ibx)
+            }
+            None => path,
+        };
+        Ok(path
+            .segment("messages")
+            .raw_segment("*")
+            .build())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FetchLatestMessagesResponse {
+    pub msgs: Vec<Email>,
+}
+
+/// How message bodies are retrieved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FetchMode {
+    /// One call to the latest messages endpoint.
+    #[default]
+    Full,
+    /// List summaries with `fetch_inbox`, then fetch each body with
+    /// at most `concurrency` calls in flight. Summaries without an
+    /// id can not be fetched and are skipped.
+    FanOut { concurrency: usize },
+}
+
+/// Position reached by a previous call, so that the next one only
+/// returns newer messages.
+///
+/// Messages are ordered by receive time, ties broken by id.
+/// Messages without a `time` are tracked by id alone. A message
+/// with neither a newer `time` nor an id can not be told apart from
+/// one already returned, so only a fresh cursor returns it.
+///
+/// ```rust
+/// use mailinator_rs::prelude::LatestCursor;
+///
+/// let cursor = LatestCursor::default();
+/// assert!(cursor.is_new(Some("a"), Some(1_000)));
+/// assert!(cursor.is_new(None, None));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatestCursor {
+    /// Receive time, in epoch milliseconds, of the newest message.
+    time: Option<u64>,
+    /// Ids of the messages received at `time`.
+    ids: HashSet<String>,
+    /// Ids of the messages received without a time.
+    undated: HashSet<String>,
+}
+
+impl LatestCursor {
+    /// Whether nothing was returned yet.
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        self.time.is_none() && self.undated.is_empty()
+    }
+
+    /// Whether a message was not returned up to this cursor.
+    #[must_use]
+    pub fn is_new(
+        &self,
+        id: Option<&str>,
+        time: Option<u64>,
+    ) -> bool {
+        match (time, self.time, id) {
+            (Some(_), None, _) => true,
+            (Some(time), Some(newest), _)
+                if time > newest =>
+            {
+                true
+            }
+            (Some(time), Some(newest), Some(id))
+                if time == newest =>
+            {
+                !self.ids.contains(id)
+            }
+            (Some(_), Some(_), _) => false,
+            (None, _, Some(id)) => {
+                !self.undated.contains(id)
+            }
+            (None, _, None) => self.is_fresh(),
+        }
+    }
+
+    /// Moves the cursor past `msgs`.
+    pub fn advance<'a>(
+        &mut self,
+        msgs: impl IntoIterator<Item = &'a Email>,
+    ) {
+        for msg in msgs {
+            let Some(time) = msg.time else {
+                self.undated.extend(msg.id.clone());
+                continue;
+            };
+            if self
+                .time
+                .map_or(true, |newest| time > newest)
+            {
+                self.time = Some(time);
+                self.ids.clear();
+            }
+            if self.time == Some(time) {
+                self.ids.extend(msg.id.clone());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct LatestMessagesRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "OptionalNameInput",
+            build = "self.inbox.optional()?"
+        )
+    )]
+    inbox: Option<InboxName>,
+    #[builder(default)]
+    mode: FetchMode,
+    /// Number of summaries listed in [`FetchMode::FanOut`].
+    #[builder(default = "50")]
+    limit: usize,
+    /// Only return messages newer than this cursor.
+    #[builder(default)]
+    cursor: Option<LatestCursor>,
+}
+
+/// Messages newest first, and the cursor to pass to the next call.
+#[derive(Debug, Clone)]
+pub struct LatestMessages {
+    pub msgs: Vec<Email>,
+    pub cursor: LatestCursor,
+}
+
+impl Mailinator {
+    /// Retrieves the latest messages with their full content.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{LatestMessagesRequestBuilder, Mailinator};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let mut cursor = None;
+    ///    loop {
+    ///        let request = LatestMessagesRequestBuilder::default()
+    ///            .domain(String::from("private"))
+    ///            .cursor(cursor.take())
+    ///            .build()
+    ///            .expect("failed to build request");
+    ///        let latest = client.fetch_latest(request).await.expect("failed to fetch");
+    ///        for email in &latest.msgs {
+    ///            println!("{:?}", email.subject);
+    ///        }
+    ///        cursor = Some(latest.cursor);
+    ///        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    ///    }
+    ///}
+    ///```
+    ///
+    /// # Errors
+    /// Returns the first error raised while listing or fetching a
+    /// message.
+    pub async fn fetch_latest(
+        &self,
+        request: LatestMessagesRequest,
+    ) -> Result<LatestMessages, Error> {
+        let LatestMessagesRequest {
+            domain,
+            inbox,
+            mode,
+            limit,
+            cursor,
+        } = request;
+        let mut cursor = cursor.unwrap_or_default();
+
+        let mut msgs = match mode {
+            FetchMode::Full => self
+                .fetch_latest_messages(
+                    FetchLatestMessagesRequest {
+                        domain,
+                        inbox,
+                    },
+                )
+                .await?
+                .msgs
+                .into_iter()
+                .filter(|m| {
+                    cursor.is_new(m.id.as_deref(), m.time)
+                })
+                .collect(),
+            FetchMode::FanOut { concurrency } => {
+                self.fan_out(
+                    domain,
+                    inbox,
+                    limit,
+                    concurrency,
+                    &cursor,
+                )
+                .await?
+            }
+        };
+        msgs.sort_by_key(|m| Reverse(m.time));
+        cursor.advance(&msgs);
+        Ok(LatestMessages { msgs, cursor })
+    }
+
+    async fn fan_out(
+        &self,
+        domain: DomainName,
+        inbox: Option<InboxName>,
+        limit: usize,
+        concurrency: usize,
+        cursor: &LatestCursor,
+    ) -> Result<Vec<Email>, Error> {
+        let selector = inbox.clone().map_or(
+            InboxSelector::All,
+            InboxSelector::Single,
+        );
+        let summaries = self
+            .fetch_inbox(
+                FetchInboxRequestBuilder::default()
+                    .domain(domain.clone())
+                    .inbox(selector)
+                    .query_params(Some(
+                        FetchInboxRequestQueryParamsBuilder::default()
+                            .limit(Some(limit))
+                            .sort(Some(Sorting::Descending))
+                            .build()?,
+                    ))
+                    .build()?,
+            )
+            .await?;
+
+        stream::iter(
+            summaries
+                .msgs
+                .into_iter()
+                .filter(|m| cursor.is_new(m.id.as_deref(), m.time))
+                .filter_map(|m| m.id),
+        )
+        .map(|id| {
+            let request = FetchMessageRequestBuilder::default()
+                .domain(domain.clone())
+                .inbox(inbox.clone().map(String::from))
+                .message_id(id)
+                .build();
+            async move { self.fetch_message(request?).await }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await
+    }
+}
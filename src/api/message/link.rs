@@ -1,21 +1,76 @@
-use crate::path::AsUrl;
+use super::{
+    otp::decode_entities, ApiMessageEndpoints, Email,
+};
+use crate::client::Mailinator;
+use crate::names::{
+    DomainName, InboxName, MessageId, NameInput,
+};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
+use futures::{stream, StreamExt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::{
+    header::LOCATION, Method, Response, StatusCode, Url,
+};
 use serde::Deserialize;
+use std::{collections::HashMap, fmt};
+
+static HREF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)href\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("valid regex")
+});
+static BARE_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"https?://[^\s<>"')\]]+"#)
+        .expect("valid regex")
+});
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchLinkRequest {
-    domain: String,
-    inbox: String,
-    message_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
 }
 
 impl AsUrl for FetchLinkRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self {
             domain,
             inbox,
             message_id,
         } = self;
-        format!("/api/v2/domains/{domain}/inboxes/{inbox}/messages/{message_id}/links")
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(inbox)
+            .segment("messages")
+            .segment(message_id)
+            .segment("links")
+            .build())
     }
 }
 
@@ -23,3 +78,690 @@ impl AsUrl for FetchLinkRequest {
 pub struct FetchLinkResponse {
     pub links: Vec<String>,
 }
+
+impl FetchLinkResponse {
+    /// Every link with its [`LinkKind`].
+    #[must_use]
+    pub fn classified(&self) -> Vec<(&str, LinkKind)> {
+        self.links
+            .iter()
+            .map(|l| (l.as_str(), LinkKind::of(l)))
+            .collect()
+    }
+}
+
+/// Query parameters tracking services carry the destination in.
+const REDIRECT_PARAMS: &[&str] = &[
+    "url",
+    "u",
+    "redirect",
+    "redirect_url",
+    "redirect_uri",
+    "target",
+    "dest",
+    "destination",
+    "link",
+    "r",
+    "to",
+];
+/// Host labels and path segments of common click trackers.
+const TRACKING_HOST_PREFIXES: &[&str] = &[
+    "click.", "clicks.", "track.", "trk.", "links.",
+    "email.",
+];
+const TRACKING_PATHS: &[&str] = &[
+    "/click",
+    "/ls/click",
+    "/track/click",
+    "/wf/click",
+    "/redirect",
+];
+const UNSUBSCRIBE_WORDS: &[&str] = &[
+    "unsubscribe",
+    "optout",
+    "opt-out",
+    "opt_out",
+    "email-preferences",
+    "manage-preferences",
+];
+
+/// Schemes that never open an application, see [`LinkKind::Other`].
+const NON_APP_SCHEMES: &[&str] = &[
+    "tel",
+    "sms",
+    "callto",
+    "javascript",
+    "data",
+    "file",
+    "ftp",
+    "blob",
+    "about",
+    "ws",
+    "wss",
+];
+
+/// What a link found in a message points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// A list unsubscribe or preference management page.
+    Unsubscribe,
+    /// A click tracker redirecting to the actual destination.
+    TrackingRedirect,
+    /// A custom scheme opening an application, e.g. `myapp://`.
+    DeepLink,
+    Mailto,
+    /// Any other scheme, e.g. `tel:`, `javascript:` or `data:`.
+    Other,
+    /// Any other http or https link.
+    Web,
+    /// A link that is not a valid url.
+    Invalid,
+}
+
+impl LinkKind {
+    /// Classifies a link from its scheme, host, path and query.
+    ///
+    /// ```rust
+    /// use mailinator_rs::prelude::LinkKind;
+    ///
+    /// assert_eq!(LinkKind::of("mailto:help@example.com"), LinkKind::Mailto);
+    /// assert_eq!(LinkKind::of("myapp://verify?token=1"), LinkKind::DeepLink);
+    /// assert_eq!(LinkKind::of("tel:+14155550123"), LinkKind::Other);
+    /// assert_eq!(
+    ///     LinkKind::of("https://click.example.com/ls/click?upn=abc"),
+    ///     LinkKind::TrackingRedirect
+    /// );
+    /// assert_eq!(
+    ///     LinkKind::of("https://example.com/email/unsubscribe?id=1"),
+    ///     LinkKind::Unsubscribe
+    /// );
+    /// ```
+    #[must_use]
+    pub fn of(link: &str) -> Self {
+        let Ok(url) = Url::parse(link.trim()) else {
+            return Self::Invalid;
+        };
+        match url.scheme() {
+            "mailto" => return Self::Mailto,
+            "http" | "https" => {}
+            // App links are written `scheme://...`.
+            scheme
+                if url.has_authority()
+                    && !NON_APP_SCHEMES
+                        .contains(&scheme) =>
+            {
+                return Self::DeepLink
+            }
+            _ => return Self::Other,
+        }
+        let path = url.path().to_ascii_lowercase();
+        let query = url
+            .query()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if UNSUBSCRIBE_WORDS
+            .iter()
+            .any(|w| path.contains(w) || query.contains(w))
+        {
+            return Self::Unsubscribe;
+        }
+        let host = url.host_str().unwrap_or_default();
+        let carries_url =
+            url.query_pairs().any(|(k, v)| {
+                REDIRECT_PARAMS.contains(&k.as_ref())
+                    && (v.starts_with("http://")
+                        || v.starts_with("https://"))
+            });
+        if carries_url
+            || TRACKING_HOST_PREFIXES
+                .iter()
+                .any(|p| host.starts_with(p))
+            || TRACKING_PATHS
+                .iter()
+                .any(|p| path.starts_with(p))
+        {
+            return Self::TrackingRedirect;
+        }
+        Self::Web
+    }
+
+    /// Whether the link can be checked over http.
+    #[must_use]
+    pub const fn is_http(self) -> bool {
+        matches!(
+            self,
+            Self::Unsubscribe
+                | Self::TrackingRedirect
+                | Self::Web
+        )
+    }
+}
+
+/// Checks the links of a message.
+///
+/// Redirects are followed one hop at a time, up to
+/// `max_redirects`, independently of the client's own redirect
+/// handling. Unsubscribe links are left alone unless
+/// `include_unsubscribe` is set.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct CheckLinksRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
+    /// Follow redirects to their destination, otherwise the first
+    /// response is reported as is.
+    #[builder(default = "true")]
+    resolve_redirects: bool,
+    /// Maximum number of redirects followed per link.
+    #[builder(default = "10")]
+    max_redirects: usize,
+    /// Send a `HEAD` request to every http link and record its
+    /// status, falling back to `GET` when `HEAD` is refused.
+    #[builder(default = "true")]
+    verify: bool,
+    #[builder(default)]
+    include_unsubscribe: bool,
+    /// Maximum number of requests in flight.
+    #[builder(default = "4")]
+    concurrency: usize,
+}
+
+/// The outcome of checking one link.
+#[derive(Debug, Clone)]
+pub struct LinkStatus {
+    pub url: String,
+    pub kind: LinkKind,
+    /// Where the link led after following redirects.
+    pub final_url: Option<String>,
+    /// Status of the last response.
+    pub status: Option<StatusCode>,
+    /// Why the link could not be requested, e.g. too many
+    /// redirects, dns or tls failures.
+    pub error: Option<String>,
+}
+
+impl LinkStatus {
+    /// Whether the link is invalid, failed to load or answered
+    /// with a 4xx or 5xx status.
+    #[must_use]
+    pub fn is_broken(&self) -> bool {
+        self.kind == LinkKind::Invalid
+            || self.error.is_some()
+            || self.status.is_some_and(|s| {
+                s.is_client_error() || s.is_server_error()
+            })
+    }
+}
+
+/// Every link of a message with its check outcome, in message
+/// order.
+#[derive(Debug, Clone, Default)]
+pub struct LinkReport {
+    pub links: Vec<LinkStatus>,
+}
+
+impl LinkReport {
+    pub fn broken(
+        &self,
+    ) -> impl Iterator<Item = &LinkStatus> {
+        self.links.iter().filter(|l| l.is_broken())
+    }
+
+    #[must_use]
+    pub fn has_broken(&self) -> bool {
+        self.broken().next().is_some()
+    }
+
+    pub fn of_kind(
+        &self,
+        kind: LinkKind,
+    ) -> impl Iterator<Item = &LinkStatus> {
+        self.links.iter().filter(move |l| l.kind == kind)
+    }
+}
+
+impl Mailinator {
+    /// Fetches the links of a message, classifies them and checks
+    /// them.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{CheckLinksRequestBuilder, Mailinator};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = CheckLinksRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(String::from("testinbox"))
+    ///        .message_id(String::from("msg_id"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    let report = client.check_links(request).await.expect("failed to fetch links");
+    ///    for link in report.broken() {
+    ///        println!("{} {:?} {:?}", link.url, link.status, link.error);
+    ///    }
+    ///}
+    ///```
+    ///
+    /// # Errors
+    /// Returns an error when the links can not be fetched. Failures
+    /// of the links themselves are recorded in the report.
+    pub async fn check_links(
+        &self,
+        request: CheckLinksRequest,
+    ) -> Result<LinkReport, Error> {
+        let CheckLinksRequest {
+            domain,
+            inbox,
+            message_id,
+            resolve_redirects,
+            max_redirects,
+            verify,
+            include_unsubscribe,
+            concurrency,
+        } = request;
+        let redirects =
+            resolve_redirects.then_some(max_redirects);
+        let links = self
+            .fetch_links(FetchLinkRequest {
+                domain,
+                inbox,
+                message_id,
+            })
+            .await?
+            .links;
+
+        let links = stream::iter(links)
+            .map(|url| {
+                let kind = LinkKind::of(&url);
+                let request = kind.is_http()
+                    && (verify
+                        || (resolve_redirects
+                            && kind == LinkKind::TrackingRedirect))
+                    && (include_unsubscribe
+                        || kind != LinkKind::Unsubscribe);
+                async move {
+                    if request {
+                        self.probe_link(url, kind, redirects)
+                            .await
+                    } else {
+                        LinkStatus {
+                            url,
+                            kind,
+                            final_url: None,
+                            status: None,
+                            error: None,
+                        }
+                    }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        Ok(LinkReport { links })
+    }
+
+    /// Requests `url`, following at most `redirects` hops, none
+    /// when `None`.
+    async fn probe_link(
+        &self,
+        url: String,
+        kind: LinkKind,
+        redirects: Option<usize>,
+    ) -> LinkStatus {
+        let mut current = url.clone();
+        let mut hops = 0;
+        let result = loop {
+            let resp =
+                match self.head_or_get(&current).await {
+                    Ok(resp) => resp,
+                    Err(e) => break Err(e.to_string()),
+                };
+            let next = redirects
+                .filter(|_| resp.status().is_redirection())
+                .and_then(|_| resp.headers().get(LOCATION))
+                .and_then(|l| l.to_str().ok())
+                .and_then(|l| resp.url().join(l).ok());
+            let Some(next) = next else {
+                break Ok((
+                    resp.status(),
+                    resp.url().to_string(),
+                ));
+            };
+            if redirects.is_some_and(|max| hops >= max) {
+                break Err(format!(
+                    "too many redirects ({hops})"
+                ));
+            }
+            hops += 1;
+            current = next.into();
+        };
+        match result {
+            Ok((status, final_url)) => LinkStatus {
+                url,
+                kind,
+                final_url: Some(final_url),
+                status: Some(status),
+                error: None,
+            },
+            Err(error) => LinkStatus {
+                url,
+                kind,
+                final_url: None,
+                status: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    async fn head_or_get(
+        &self,
+        url: &str,
+    ) -> Result<Response, Error> {
+        let resp = self
+            .request_external(Method::HEAD, url)
+            .await?;
+        // Some servers refuse HEAD altogether.
+        if matches!(
+            resp.status(),
+            StatusCode::METHOD_NOT_ALLOWED
+                | StatusCode::NOT_IMPLEMENTED
+        ) {
+            return self
+                .request_external(Method::GET, url)
+                .await;
+        }
+        Ok(resp)
+    }
+}
+
+/// Describes the link to pick out of a message, every condition
+/// set must hold.
+///
+/// ```rust
+/// use mailinator_rs::prelude::LinkPattern;
+///
+/// let pattern = LinkPattern::default()
+///     .host("app.example.com")
+///     .path_prefix("/auth/magic")
+///     .require_query("token");
+/// let link = pattern
+///     .find_in([
+///         "https://app.example.com/settings",
+///         "https://app.example.com/auth/magic?token=abc123&next=%2Fhome",
+///     ])
+///     .unwrap();
+/// assert_eq!(link.param("token"), Some("abc123"));
+/// assert_eq!(link.param("next"), Some("/home"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkPattern {
+    host: Option<String>,
+    path_prefix: Option<String>,
+    required_query: Vec<String>,
+}
+
+impl LinkPattern {
+    /// Host equals `host`, ignoring case.
+    #[must_use]
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Path starts with `prefix`.
+    #[must_use]
+    pub fn path_prefix(
+        mut self,
+        prefix: impl Into<String>,
+    ) -> Self {
+        self.path_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Query has a non empty `name` parameter.
+    #[must_use]
+    pub fn require_query(
+        mut self,
+        name: impl Into<String>,
+    ) -> Self {
+        self.required_query.push(name.into());
+        self
+    }
+
+    /// The parsed link, if it matches.
+    #[must_use]
+    pub fn matches(
+        &self,
+        link: &str,
+    ) -> Option<MatchedLink> {
+        let url = Url::parse(link.trim()).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let host_ok =
+            self.host.as_deref().map_or(true, |h| {
+                url.host_str().is_some_and(|uh| {
+                    uh.eq_ignore_ascii_case(h)
+                })
+            });
+        let path_ok = self
+            .path_prefix
+            .as_deref()
+            .map_or(true, |p| url.path().starts_with(p));
+        let query: HashMap<String, String> =
+            url.query_pairs().into_owned().collect();
+        let query_ok =
+            self.required_query.iter().all(|name| {
+                query
+                    .get(name)
+                    .is_some_and(|v| !v.is_empty())
+            });
+        (host_ok && path_ok && query_ok).then(|| {
+            MatchedLink {
+                url: url.to_string(),
+                query,
+            }
+        })
+    }
+
+    /// The single link matching the pattern.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoMatchingLink`] when no link matches and
+    /// [`Error::AmbiguousLink`] when distinct links match.
+    pub fn find_in<I, S>(
+        &self,
+        links: I,
+    ) -> Result<MatchedLink, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let links: Vec<String> = links
+            .into_iter()
+            .map(|l| l.as_ref().to_owned())
+            .collect();
+        let mut found: Vec<MatchedLink> = Vec::new();
+        for link in
+            links.iter().filter_map(|l| self.matches(l))
+        {
+            if !found.iter().any(|f| f.url == link.url) {
+                found.push(link);
+            }
+        }
+        match found.len() {
+            0 => Err(Error::NoMatchingLink {
+                pattern: self.to_string(),
+                links,
+            }),
+            1 => Ok(found.remove(0)),
+            _ => Err(Error::AmbiguousLink {
+                pattern: self.to_string(),
+                matches: found
+                    .into_iter()
+                    .map(|f| f.url)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for LinkPattern {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}{}*",
+            self.host.as_deref().unwrap_or("*"),
+            self.path_prefix.as_deref().unwrap_or("/"),
+        )?;
+        if !self.required_query.is_empty() {
+            write!(
+                f,
+                "?{}",
+                self.required_query.join("&")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A link picked by a [`LinkPattern`], with its decoded query
+/// parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedLink {
+    pub url: String,
+    pub query: HashMap<String, String>,
+}
+
+impl MatchedLink {
+    #[must_use]
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(String::as_str)
+    }
+}
+
+impl Email {
+    /// Links of the html and plain text bodies, in order and
+    /// without duplicates. Html entities in `href` values are
+    /// decoded.
+    #[must_use]
+    pub fn links(&self) -> Vec<String> {
+        let mut links: Vec<String> = Vec::new();
+        let mut push = |link: String| {
+            if !link.is_empty() && !links.contains(&link) {
+                links.push(link);
+            }
+        };
+        if let Some(html) = self.html_body() {
+            for caps in HREF.captures_iter(&html.text) {
+                if let Some(href) =
+                    caps.get(1).or_else(|| caps.get(2))
+                {
+                    push(decode_entities(
+                        href.as_str().trim(),
+                    ));
+                }
+            }
+        }
+        if let Some(text) = self.text_body() {
+            for url in BARE_URL.find_iter(&text.text) {
+                push(
+                    url.as_str()
+                        .trim_end_matches([
+                            '.', ',', ';', ':', '!', '?',
+                        ])
+                        .to_owned(),
+                );
+            }
+        }
+        links
+    }
+
+    /// The single link of the message matching `pattern`.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoMatchingLink`] or [`Error::AmbiguousLink`].
+    pub fn find_link(
+        &self,
+        pattern: &LinkPattern,
+    ) -> Result<MatchedLink, Error> {
+        pattern.find_in(self.links())
+    }
+}
+
+impl Mailinator {
+    /// Fetches the links of a message and returns the single one
+    /// matching `pattern`.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchLinkRequestBuilder, LinkPattern, Mailinator};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchLinkRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(String::from("testinbox"))
+    ///        .message_id(String::from("msg_id"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///    let pattern = LinkPattern::default()
+    ///        .host("app.example.com")
+    ///        .path_prefix("/confirm")
+    ///        .require_query("token");
+    ///
+    ///    match client.find_link(request, &pattern).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(link) => println!("{:?}", link.param("token")),
+    ///    }
+    ///}
+    ///```
+    ///
+    /// # Errors
+    /// Returns an error when the links can not be fetched, and
+    /// [`Error::NoMatchingLink`] or [`Error::AmbiguousLink`] when
+    /// not exactly one link matches.
+    pub async fn find_link(
+        &self,
+        request: FetchLinkRequest,
+        pattern: &LinkPattern,
+    ) -> Result<MatchedLink, Error> {
+        pattern
+            .find_in(self.fetch_links(request).await?.links)
+    }
+}
@@ -0,0 +1,717 @@
+use super::{headers::Headers, Email, Part};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use encoding_rs::{Encoding, UTF_8};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static ENCODED_WORD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"=\?([^?\s]+)\?([bBqQ])\?([^?\s]*)\?=")
+        .expect("valid regex")
+});
+
+/// A parsed `content-type` (or `content-disposition`) header value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType {
+    /// Lower cased value before the parameters, e.g. `text/html`.
+    pub value: String,
+    /// Parameters with lower cased names and unquoted values.
+    pub params: Vec<(String, String)>,
+}
+
+impl ContentType {
+    #[must_use]
+    pub fn parse(header: &str) -> Self {
+        let mut fields =
+            split_unquoted(header, ';').into_iter();
+        let value = fields
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let params = fields
+            .filter_map(|field| {
+                let (name, value) =
+                    field.split_once('=')?;
+                Some((
+                    name.trim().to_ascii_lowercase(),
+                    unquote(value.trim()),
+                ))
+            })
+            .collect();
+        Self { value, params }
+    }
+
+    #[must_use]
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// A parameter value, joining RFC 2231 continuations
+    /// (`name*0`, `name*1`...) and decoding extended values
+    /// (`name*=utf-8''...`) as well as RFC 2047 encoded words.
+    #[must_use]
+    pub fn param_decoded(
+        &self,
+        name: &str,
+    ) -> Option<String> {
+        if let Some(value) = self.param(&format!("{name}*"))
+        {
+            let (charset, rest) = split_charset(value);
+            return Some(decode_with_charset(
+                &percent_decode(rest),
+                charset,
+            ));
+        }
+        let mut bytes = Vec::new();
+        let mut charset = None;
+        for idx in 0.. {
+            if let Some(value) =
+                self.param(&format!("{name}*{idx}*"))
+            {
+                let value = if idx == 0 {
+                    let (cs, rest) = split_charset(value);
+                    charset = cs;
+                    rest
+                } else {
+                    value
+                };
+                bytes.extend(percent_decode(value));
+            } else if let Some(value) =
+                self.param(&format!("{name}*{idx}"))
+            {
+                bytes.extend_from_slice(value.as_bytes());
+            } else if idx == 0 {
+                return self
+                    .param(name)
+                    .map(decode_encoded_words);
+            } else {
+                break;
+            }
+        }
+        Some(decode_with_charset(&bytes, charset))
+    }
+}
+
+/// Splits the `charset'language'` prefix of an RFC 2231 extended
+/// value.
+fn split_charset(value: &str) -> (Option<&str>, &str) {
+    let mut fields = value.splitn(3, '\'');
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some(charset), Some(_), Some(rest)) => {
+            ((!charset.is_empty()).then_some(charset), rest)
+        }
+        _ => (None, value),
+    }
+}
+
+fn decode_with_charset(
+    bytes: &[u8],
+    charset: Option<&str>,
+) -> String {
+    charset
+        .and_then(|c| Encoding::for_label(c.as_bytes()))
+        .unwrap_or(UTF_8)
+        .decode(bytes)
+        .0
+        .into_owned()
+}
+
+fn percent_decode(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| {
+                Some(
+                    hex_value(hex[0])? << 4
+                        | hex_value(hex[1])?,
+                )
+            });
+        if let Some(byte) = decoded {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Decoded text of a `text/plain` or `text/html` part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextBody {
+    /// Index of the part in [`Email::parts`].
+    pub part: usize,
+    pub mime_type: String,
+    pub charset: Option<String>,
+    pub text: String,
+}
+
+/// Decoded content of an attachment or inline image part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedFile {
+    /// Index of the part in [`Email::parts`].
+    pub part: usize,
+    pub mime_type: String,
+    pub filename: Option<String>,
+    /// The `content-id` without its angle brackets, as referenced
+    /// by `cid:` urls.
+    pub content_id: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    /// A header value, looked up ignoring case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.as_ref()?.get(name)
+    }
+
+    /// A header value with RFC 2047 encoded words decoded.
+    #[must_use]
+    pub fn header_decoded(
+        &self,
+        name: &str,
+    ) -> Option<String> {
+        self.headers.as_ref()?.get_decoded(name)
+    }
+
+    /// The parsed `content-type`, `text/plain` when missing.
+    #[must_use]
+    pub fn content_type(&self) -> ContentType {
+        ContentType::parse(
+            self.header("content-type")
+                .unwrap_or("text/plain"),
+        )
+    }
+
+    #[must_use]
+    pub fn content_disposition(
+        &self,
+    ) -> Option<ContentType> {
+        self.header("content-disposition")
+            .map(ContentType::parse)
+    }
+
+    /// The decoded file name from `content-disposition`, falling
+    /// back to the `name` parameter of `content-type`.
+    #[must_use]
+    pub fn filename(&self) -> Option<String> {
+        self.content_disposition()
+            .and_then(|d| d.param_decoded("filename"))
+            .or_else(|| {
+                self.content_type().param_decoded("name")
+            })
+    }
+
+    /// The body with its `content-transfer-encoding` reverted.
+    ///
+    /// Bodies that fail to decode are returned as is.
+    #[must_use]
+    pub fn decoded_bytes(&self) -> Option<Vec<u8>> {
+        let body = self.body.as_ref()?;
+        Some(decode_transfer_encoding(
+            body.as_bytes(),
+            self.header("content-transfer-encoding"),
+        ))
+    }
+
+    /// The body decoded from its transfer encoding and charset,
+    /// invalid sequences being replaced.
+    #[must_use]
+    pub fn decoded_text(&self) -> Option<String> {
+        let bytes = self.decoded_bytes()?;
+        Some(decode_charset(&bytes, &self.content_type()))
+    }
+
+    fn is_attachment(&self) -> bool {
+        self.content_disposition()
+            .is_some_and(|d| d.value == "attachment")
+    }
+
+    fn is_inline_image(&self) -> bool {
+        !self.is_attachment()
+            && self
+                .content_type()
+                .value
+                .starts_with("image/")
+            && (self.header("content-id").is_some()
+                || self
+                    .content_disposition()
+                    .is_some_and(|d| d.value == "inline"))
+    }
+
+    fn to_embedded_file(
+        &self,
+        part: usize,
+    ) -> EmbeddedFile {
+        EmbeddedFile {
+            part,
+            mime_type: self.content_type().value,
+            filename: self.filename(),
+            content_id: self.header("content-id").map(
+                |id| {
+                    id.trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_owned()
+                },
+            ),
+            data: self.decoded_bytes().unwrap_or_default(),
+        }
+    }
+}
+
+impl Email {
+    /// A header value, looked up ignoring case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.as_ref()?.get(name)
+    }
+
+    /// A header value with RFC 2047 encoded words decoded.
+    #[must_use]
+    pub fn header_decoded(
+        &self,
+        name: &str,
+    ) -> Option<String> {
+        self.headers.as_ref()?.get_decoded(name)
+    }
+
+    /// The subject with RFC 2047 encoded words decoded, read from
+    /// the `Subject` header when the field is missing.
+    #[must_use]
+    pub fn decoded_subject(&self) -> Option<String> {
+        self.subject
+            .as_deref()
+            .map(decode_encoded_words)
+            .or_else(|| self.header_decoded("subject"))
+    }
+
+    /// The first non attachment `text/plain` part, decoded.
+    #[must_use]
+    pub fn text_body(&self) -> Option<TextBody> {
+        self.text_part("text/plain")
+    }
+
+    /// The first non attachment `text/html` part, decoded.
+    #[must_use]
+    pub fn html_body(&self) -> Option<TextBody> {
+        self.text_part("text/html")
+    }
+
+    /// Images embedded in the html body, usually referenced with
+    /// `cid:` urls.
+    #[must_use]
+    pub fn inline_images(&self) -> Vec<EmbeddedFile> {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_inline_image())
+            .map(|(idx, p)| p.to_embedded_file(idx))
+            .collect()
+    }
+
+    /// Parts sent as attachments, or carrying a file name without
+    /// being a body or an inline image.
+    #[must_use]
+    pub fn attachments(&self) -> Vec<EmbeddedFile> {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| {
+                p.is_attachment()
+                    || (p.filename().is_some()
+                        && !p.is_inline_image())
+            })
+            .map(|(idx, p)| p.to_embedded_file(idx))
+            .collect()
+    }
+
+    fn text_part(
+        &self,
+        mime_type: &str,
+    ) -> Option<TextBody> {
+        self.parts.iter().enumerate().find_map(
+            |(idx, part)| {
+                let content_type = part.content_type();
+                if content_type.value != mime_type
+                    || part.is_attachment()
+                {
+                    return None;
+                }
+                Some(TextBody {
+                    part: idx,
+                    charset: content_type
+                        .param("charset")
+                        .map(str::to_owned),
+                    mime_type: content_type.value,
+                    text: part.decoded_text()?,
+                })
+            },
+        )
+    }
+}
+
+/// Reverts a `content-transfer-encoding`, returning bodies that
+/// fail to decode as is.
+#[must_use]
+pub fn decode_transfer_encoding(
+    body: &[u8],
+    encoding: Option<&str>,
+) -> Vec<u8> {
+    let encoding =
+        encoding.map(|e| e.trim().to_ascii_lowercase());
+    match encoding.as_deref() {
+        Some("base64") => {
+            let compact: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|c| !c.is_ascii_whitespace())
+                .collect();
+            STANDARD
+                .decode(compact)
+                .unwrap_or_else(|_| body.to_vec())
+        }
+        Some("quoted-printable") => {
+            decode_quoted_printable(body)
+        }
+        _ => body.to_vec(),
+    }
+}
+
+/// Decodes text from the `charset` parameter, utf-8 by default,
+/// replacing invalid sequences.
+fn decode_charset(
+    bytes: &[u8],
+    content_type: &ContentType,
+) -> String {
+    decode_with_charset(
+        bytes,
+        content_type.param("charset"),
+    )
+}
+
+/// Reverts quoted-printable encoding, leaving malformed escapes
+/// untouched.
+#[must_use]
+pub fn decode_quoted_printable(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'=')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten();
+        let decoded = escaped.and_then(|hex| {
+            Some(
+                hex_value(hex[0])? << 4
+                    | hex_value(hex[1])?,
+            )
+        });
+        match (escaped, decoded) {
+            (_, Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            // Soft line breaks
+            (Some([b'\r', b'\n']), _) => i += 3,
+            (Some([b'\n', _]), _) => i += 2,
+            _ if &bytes[i..] == b"=\n" => i += 2,
+            _ => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Decodes RFC 2047 encoded words, e.g. `=?utf-8?Q?Caf=C3=A9?=`,
+/// dropping the whitespace between adjacent ones. Words with an
+/// unknown charset or malformed content are left as is.
+#[must_use]
+pub fn decode_encoded_words(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut last = 0;
+    let mut after_word = false;
+    for caps in ENCODED_WORD.captures_iter(input) {
+        let word = caps.get(0).expect("whole match");
+        let Some(decoded) = decode_encoded_word(
+            &caps[1], &caps[2], &caps[3],
+        ) else {
+            continue;
+        };
+        let between = &input[last..word.start()];
+        if !(after_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&decoded);
+        last = word.end();
+        after_word = true;
+    }
+    out.push_str(&input[last..]);
+    out
+}
+
+fn decode_encoded_word(
+    charset: &str,
+    encoding: &str,
+    text: &str,
+) -> Option<String> {
+    // RFC 2231 allows a language suffix, e.g. `utf-8*en`.
+    let charset = charset.split('*').next()?;
+    let charset = Encoding::for_label(charset.as_bytes())?;
+    let bytes = if encoding.eq_ignore_ascii_case("b") {
+        STANDARD.decode(text).ok()?
+    } else {
+        decode_quoted_printable(
+            text.replace('_', " ").as_bytes(),
+        )
+    };
+    Some(charset.decode(&bytes).0.into_owned())
+}
+
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Splits on `sep` outside of double quotes.
+pub fn split_unquoted(input: &str, sep: char) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (idx, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == sep && !quoted => {
+                fields.push(&input[start..idx]);
+                start = idx + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    fields.push(&input[start..]);
+    fields
+}
+
+pub fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .map_or_else(
+            || value.to_owned(),
+            |v| {
+                v.replace("\\\"", "\"")
+                    .replace("\\\\", "\\")
+            },
+        )
+}
+
+/// A node of a parsed MIME message: a leaf part or a multipart
+/// container with its children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimeNode {
+    /// Unfolded headers in their original order and case.
+    pub headers: Headers,
+    /// The raw body, still transfer encoded. Empty for multipart
+    /// nodes, whose content lives in `children`.
+    pub body: Vec<u8>,
+    pub children: Vec<Self>,
+}
+
+impl MimeNode {
+    /// Parses a message or part, falling back to a single leaf when
+    /// a multipart boundary is missing.
+    #[must_use]
+    pub fn parse(raw: &[u8]) -> Self {
+        let (head, body) = split_head(raw);
+        let headers = parse_headers(head);
+        let mut node = Self {
+            headers,
+            body: Vec::new(),
+            children: Vec::new(),
+        };
+        let content_type = node.content_type();
+        let boundary = content_type
+            .value
+            .starts_with("multipart/")
+            .then(|| content_type.param("boundary"))
+            .flatten();
+        match boundary
+            .map(|b| split_multipart(body, b.as_bytes()))
+        {
+            Some(parts) if !parts.is_empty() => {
+                node.children = parts
+                    .into_iter()
+                    .map(Self::parse)
+                    .collect();
+            }
+            _ => node.body = body.to_vec(),
+        }
+        node
+    }
+
+    /// The first value of a header, looked up ignoring case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)
+    }
+
+    /// Every value of a header, e.g. all `Received` lines.
+    #[must_use]
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.headers.get_all(name).collect()
+    }
+
+    /// The parsed `content-type`, `text/plain` when missing.
+    #[must_use]
+    pub fn content_type(&self) -> ContentType {
+        ContentType::parse(
+            self.header("content-type")
+                .unwrap_or("text/plain"),
+        )
+    }
+
+    #[must_use]
+    pub fn is_multipart(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    /// The body with its `content-transfer-encoding` reverted.
+    #[must_use]
+    pub fn decoded_body(&self) -> Vec<u8> {
+        decode_transfer_encoding(
+            &self.body,
+            self.header("content-transfer-encoding"),
+        )
+    }
+
+    /// The decoded body as text, using the `charset` parameter.
+    #[must_use]
+    pub fn decoded_text(&self) -> String {
+        decode_charset(
+            &self.decoded_body(),
+            &self.content_type(),
+        )
+    }
+
+    /// Every leaf part, depth first.
+    #[must_use]
+    pub fn leaves(&self) -> Vec<&Self> {
+        if self.children.is_empty() {
+            vec![self]
+        } else {
+            self.children
+                .iter()
+                .flat_map(Self::leaves)
+                .collect()
+        }
+    }
+}
+
+/// Splits headers from body at the first empty line.
+fn split_head(raw: &[u8]) -> (&[u8], &[u8]) {
+    let crlf = find(raw, b"\r\n\r\n").map(|i| (i, 4));
+    let lf = find(raw, b"\n\n").map(|i| (i, 2));
+    let split = match (crlf, lf) {
+        (Some(a), Some(b)) => {
+            Some(if a.0 < b.0 { a } else { b })
+        }
+        (a, b) => a.or(b),
+    };
+    split.map_or((raw, &[][..]), |(i, len)| {
+        (&raw[..i], &raw[i + len..])
+    })
+}
+
+fn parse_headers(head: &[u8]) -> Headers {
+    let head = String::from_utf8_lossy(head);
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in head.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) =
+            line.split_once(':')
+        {
+            headers.push((
+                name.trim().to_owned(),
+                value.trim().to_owned(),
+            ));
+        }
+    }
+    headers.into_iter().collect()
+}
+
+/// The parts between `--boundary` delimiter lines, preamble and
+/// epilogue excluded.
+fn split_multipart<'a>(
+    body: &'a [u8],
+    boundary: &[u8],
+) -> Vec<&'a [u8]> {
+    let mut delimiter = b"--".to_vec();
+    delimiter.extend_from_slice(boundary);
+
+    let mut parts = Vec::new();
+    let mut current: Option<usize> = None;
+    let mut pos = 0;
+    while pos < body.len() {
+        let end = find(&body[pos..], b"\n")
+            .map_or(body.len(), |i| pos + i + 1);
+        let line = trim_eol(&body[pos..end]);
+        // Only padding may follow the boundary, so a longer boundary
+        // of a nested part sharing its prefix is not a delimiter.
+        let rest = line
+            .strip_prefix(delimiter.as_slice())
+            .map(trim_padding);
+        if let Some(rest @ (b"" | b"--")) = rest {
+            if let Some(start) = current {
+                // The line break before a delimiter belongs to it.
+                parts.push(trim_eol(&body[start..pos]));
+            }
+            if rest == b"--" {
+                return parts;
+            }
+            current = Some(end);
+        }
+        pos = end;
+    }
+    if let Some(start) = current {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+fn trim_padding(line: &[u8]) -> &[u8] {
+    let len = line
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(0, |i| i + 1);
+    &line[..len]
+}
+
+fn trim_eol(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
@@ -1,112 +1,210 @@
 use crate::client::Mailinator;
-use crate::path::AsUrl;
+use crate::names::{
+    DomainName, InboxName, MessageId, NameInput,
+    OptionalNameInput,
+};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
 use async_trait::async_trait;
-use eyre::Report;
+use chrono::{DateTime, Utc};
+
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
+use tokio::io::AsyncWrite;
+
+pub mod address;
 pub mod attachment;
+pub mod headers;
+
 pub mod inbox;
+pub mod latest;
 pub mod link;
+pub mod mime;
+pub mod otp;
+pub mod paginate;
+pub mod sms;
+pub mod smtp;
+pub mod wait;
+pub mod watch;
 
 use self::{
     attachment::{
+        AttachmentFile, AttachmentInfo,
         FetchAttachmentRequest,
         FetchListOfAttachmentRequest,
         FetchListOfAttachmentResponse,
     },
+    headers::Headers,
     inbox::{FetchInboxRequest, FetchInboxResponse},
+    latest::{
+        FetchLatestMessagesRequest,
+        FetchLatestMessagesResponse,
+    },
     link::{FetchLinkRequest, FetchLinkResponse},
+    mime::MimeNode,
+    sms::{
+        FetchSmsInboxRequest, FetchSmsMessageRequest,
+        SmsInbox, SmsMessage,
+    },
+    smtp::SmtpLogResponse,
 };
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct FetchMessageRequest {
-    domain: String,
-    inbox: Option<String>,
-    message_id: String,
-}
-
-#[derive(Debug, Builder, Default)]
-pub struct FetchSmsMessageRequest {
-    domain: String,
-    sms_number: u64,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "OptionalNameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: Option<InboxName>,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
 }
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct DeleteAllDomainMessageRequest {
-    domain: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
 }
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct DeleteAllInboxMessageRequest {
-    domain: String,
-    inbox: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
 }
 
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct DeleteMessageRequest {
-    domain: String,
-    inbox: String,
-    message_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
 }
 
 impl AsUrl for FetchMessageRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self {
             domain,
             inbox,
             message_id,
         } = self;
-        inbox.map_or_else(|| format!(
-            "/api/v2/domains/{domain}/messages/{message_id}"), 
-            |ibx|
-             format!("/api/v2/domains/{domain}/inboxes/{ibx}/messages/{message_id}"))
-    }
-}
-
-impl AsUrl for FetchSmsMessageRequest {
-    fn as_url_path(self) -> String {
-        let Self { domain, sms_number } = self;
-        format!(
-            "/api/v2/domains/{domain}/inboxes/{sms_number}"
-        )
+        let path = ApiPath::v2()
+            .segment("domains")
+            .segment(domain);
+        let path = match inbox {
+            Some(ibx) => {
+                path.segment("inboxes").segment(ibx)
+            }
+            None => path,
+        };
+        Ok(path
+            .segment("messages")
+            .segment(message_id)
+            .build())
     }
 }
 
 impl AsUrl for DeleteAllDomainMessageRequest {
-    fn as_url_path(self) -> String {
-        format!(
-            "/api/v2/domains/{domain}/inboxes/",
-            domain = self.domain
-        )
+    fn as_url_path(self) -> Result<String, Error> {
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(self.domain)
+            .segment("inboxes")
+            .trailing_slash()
+            .build())
     }
 }
 
 impl AsUrl for DeleteAllInboxMessageRequest {
-    fn as_url_path(self) -> String {
-        format!(
-            "/api/v2/domains/{domain}/inboxes/{inbox}",
-            domain = self.domain,
-            inbox = self.inbox
-        )
+    fn as_url_path(self) -> Result<String, Error> {
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(self.domain)
+            .segment("inboxes")
+            .segment(self.inbox)
+            .build())
     }
 }
 
 impl AsUrl for DeleteMessageRequest {
-    fn as_url_path(self) -> String {
-        format!(
-            "/api/v2/domains/{domain}/inboxes/{inbox}/messages/{message_id}",
-            domain = self.domain,
-            inbox = self.inbox,
-            message_id = self.message_id
-        )
+    fn as_url_path(self) -> Result<String, Error> {
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(self.domain)
+            .segment("inboxes")
+            .segment(self.inbox)
+            .segment("messages")
+            .segment(self.message_id)
+            .build())
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct Part {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, String>>,
+    pub headers: Option<Headers>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
 }
@@ -116,7 +214,7 @@ pub struct Email {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fromfull: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, String>>,
+    pub headers: Option<Headers>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subject: Option<String>,
     pub parts: Vec<Part>,
@@ -133,11 +231,12 @@ pub struct Email {
 }
 
 #[derive(Debug, Clone, Serialize, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct NewEmail {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fromfull: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, String>>,
+    pub headers: Option<Headers>,
     pub subject: String,
     pub parts: Vec<Part>,
     pub from: String,
@@ -152,15 +251,74 @@ pub struct DeleteMessageResponse {
 }
 
 #[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct InjectMessageRequest {
-    pub domain: String,
-    pub inbox: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    pub domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    pub inbox: InboxName,
 }
 
 impl AsUrl for InjectMessageRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self { domain, inbox } = self;
-        format!("/api/v2/domains/{domain}/inboxes/{inbox}")
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(inbox)
+            .build())
+    }
+}
+
+/// The original source of a message, as received by Mailinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawMessage {
+    pub data: Vec<u8>,
+}
+
+impl Email {
+    /// When Mailinator received the message.
+    #[must_use]
+    pub fn received_at(&self) -> Option<DateTime<Utc>> {
+        inbox::from_millis(self.time)
+    }
+
+    /// Whether the message was received at or after `since` and
+    /// before `until`, each bound being optional.
+    #[must_use]
+    pub fn received_within(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> bool {
+        inbox::within(self.received_at(), since, until)
+    }
+}
+
+impl RawMessage {
+    /// Parses the source into a tree of MIME parts.
+    #[must_use]
+    pub fn to_mime_tree(&self) -> MimeNode {
+        MimeNode::parse(&self.data)
+    }
+
+    /// The source as text, invalid utf-8 being replaced.
+    #[must_use]
+    pub fn as_text(&self) -> String {
+        String::from_utf8_lossy(&self.data).into_owned()
     }
 }
 
@@ -173,23 +331,26 @@ pub struct InjectMessageResponse {
 /// This trait provides the methods for the Mailinator Message Api
 ///
 /// # Errors
-/// Any error will be wrapped as a [`eyre::Report`]
+/// Any failure is reported as a [`crate::Error`]
 #[async_trait]
 pub trait ApiMessageEndpoints {
     ///Fetch Inbox (aka Fetch Message Summaries)
     ///
     /// This endpoint retrieves a list of messages summaries. You can retreive a list by inbox, inboxes, or entire domain.
     ///
-    /// ```rust
+    /// ```rust,no_run
     ///use mailinator_rs::prelude::{
-    ///    FetchInboxRequestBuilder, FetchInboxRequestQueryParamsBuilder, MailinatorClient, MessageApi,Sorting
+    ///    FetchInboxRequestBuilder, FetchInboxRequestQueryParamsBuilder, Mailinator, ApiMessageEndpoints,Sorting
     ///};
     ///
     ///
     ///#[tokio::main]
     ///async fn main() {
     ///    let team_api_token = String::from("XXXXX-XXXXXX");
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///    let domain = String::from("example.com");
     ///    let inbox = String::from("testinbox");
     ///
@@ -215,13 +376,13 @@ pub trait ApiMessageEndpoints {
     async fn fetch_inbox(
         &self,
         request: FetchInboxRequest,
-    ) -> Result<FetchInboxResponse, Report>;
+    ) -> Result<FetchInboxResponse, Error>;
     /// Fetch Message
     ///
     /// This endpoint retrieves a specific message by id.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{FetchMessageRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
@@ -229,7 +390,10 @@ pub trait ApiMessageEndpoints {
     ///    let domain = String::from("example.com");
     ///    let msg_id = String::from("xxx-id-xx-msg");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = FetchMessageRequestBuilder::default()
     ///        .domain(domain)
@@ -246,45 +410,179 @@ pub trait ApiMessageEndpoints {
     async fn fetch_message(
         &self,
         request: FetchMessageRequest,
-    ) -> Result<Email, Report>;
-    /// Fetch an SMS Messages
+    ) -> Result<Email, Error>;
+    /// Fetch Latest Messages
+    ///
+    /// This endpoint retrieves the latest messages of a domain, or of an inbox, with their full content.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchLatestMessagesRequestBuilder, Mailinator, ApiMessageEndpoints};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchLatestMessagesRequestBuilder::default()
+    ///        .domain(String::from("private"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.fetch_latest_messages(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(latest) => println!("{} messages", latest.msgs.len()),
+    ///    }
+    ///}
+    ///```
+    async fn fetch_latest_messages(
+        &self,
+        request: FetchLatestMessagesRequest,
+    ) -> Result<FetchLatestMessagesResponse, Error>;
+    /// Fetch Message (Raw)
     ///
-    /// SMS messages go into an inbox by the name of their phone number. Retrieving them is the same as any other message,
-    /// simply use the phone number as the Inbox you are fetching.
+    /// This endpoint retrieves the original source of a message, with its exact headers, signatures and multipart
+    /// boundaries.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{FetchSmsMessageRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchMessageRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(Some(String::from("testinbox")))
+    ///        .message_id(String::from("msg_id"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.fetch_raw_message(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(raw) => println!("{:?}", raw.to_mime_tree().header("dkim-signature")),
+    ///    }
+    ///}
+    ///```
+    async fn fetch_raw_message(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<RawMessage, Error>;
+    /// Fetch SMTP Log
+    ///
+    /// This endpoint retrieves the smtp transaction log of a message: connecting ip, HELO, MAIL FROM, RCPT TO and
+    /// the time of each step.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchMessageRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(Some(String::from("testinbox")))
+    ///        .message_id(String::from("msg_id"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.fetch_smtp_log(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(log) => println!("{:?} {:?}", log.connecting_ip(), log.mail_from()),
+    ///    }
+    ///}
+    ///```
+    async fn fetch_smtp_log(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<SmtpLogResponse, Error>;
+    /// Fetch an SMS Inbox
+    ///
+    /// SMS messages go into an inbox by the name of their phone number. This endpoint lists the messages received by a number.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchSmsInboxRequestBuilder, Mailinator, ApiMessageEndpoints, SmsNumber};
     ///
     ///#[tokio::main]
     ///async fn main() {
     ///    let team_api_token = String::from("XXXXX-XXXXXX");
     ///    let domain = String::from("example.com");
-    ///    let sms_number: u64 = 12345678;
+    ///    let sms_number: SmsNumber = "+14155550123".parse().expect("invalid number");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchSmsInboxRequestBuilder::default()
+    ///        .domain(domain)
+    ///        .sms_number(sms_number)
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.fetch_sms_inbox(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(inbox) => {
+    ///            for sms in inbox.msgs {
+    ///                println!("{} {}", sms.from, sms.text);
+    ///            }
+    ///        }
+    ///    }
+    ///}
+    ///```
+    async fn fetch_sms_inbox(
+        &self,
+        request: FetchSmsInboxRequest,
+    ) -> Result<SmsInbox, Error>;
+    /// Fetch an SMS Message
+    ///
+    /// Retrieving an SMS message is the same as any other message, simply use the phone number as the Inbox you are fetching.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchSmsMessageRequestBuilder, Mailinator, ApiMessageEndpoints, SmsNumber};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let team_api_token = String::from("XXXXX-XXXXXX");
+    ///    let domain = String::from("example.com");
+    ///    let sms_number: SmsNumber = "+14155550123".parse().expect("invalid number");
+    ///
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = FetchSmsMessageRequestBuilder::default()
     ///        .domain(domain)
     ///        .sms_number(sms_number)
+    ///        .message_id(String::from("msg_id"))
     ///        .build()
     ///        .expect("failed to build request");
     ///
     ///    match client.fetch_sms_message(request).await {
     ///        Err(e) => println!("Error: {e}"),
-    ///        Ok(msg) => println!("{msg:?}"),
+    ///        Ok(sms) => println!("{:?}", sms.verification_code()),
     ///    }
     ///}
     ///```
     async fn fetch_sms_message(
         &self,
         request: FetchSmsMessageRequest,
-    ) -> Result<Email, Report>;
+    ) -> Result<SmsMessage, Error>;
     ///Fetch List of Attachments
     ///
     ///This endpoint retrieves a list of attachments for a message. Note attachments are expected to be in Email format.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{FetchListOfAttachmentRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchListOfAttachmentRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
@@ -293,7 +591,10 @@ pub trait ApiMessageEndpoints {
     ///    let inbox = String::from("testinbox");
     ///    let msg_id = String::from("msg_id");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = FetchListOfAttachmentRequestBuilder::default()
     ///        .domain(domain)
@@ -311,13 +612,13 @@ pub trait ApiMessageEndpoints {
     async fn fetch_list_of_attachments(
         &self,
         request: FetchListOfAttachmentRequest,
-    ) -> Result<FetchListOfAttachmentResponse, Report>;
+    ) -> Result<FetchListOfAttachmentResponse, Error>;
     /// Fetch Links
     ///
     /// This endpoint retrieves all links found within a given email
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{FetchLinkRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{FetchLinkRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
@@ -326,7 +627,10 @@ pub trait ApiMessageEndpoints {
     ///    let inbox = String::from("testinbox");
     ///    let msg_id = String::from("msg_id");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = FetchLinkRequestBuilder::default()
     ///        .domain(domain)
@@ -345,14 +649,15 @@ pub trait ApiMessageEndpoints {
     async fn fetch_links(
         &self,
         request: FetchLinkRequest,
-    ) -> Result<FetchLinkResponse, Report>;
+    ) -> Result<FetchLinkResponse, Error>;
     /// Fetch Attachment
     ///
-    ///This endpoint retrieves a list of attachments for a message. Note attachments are expected to be in Email format.
+    /// This endpoint retrieves the content of an attachment, by id or by file name, in memory. The file name and
+    /// content type are read from the response headers.
     ///
-    /// ```rust
+    /// ```rust,no_run
     ///use mailinator_rs::prelude::{
-    ///    AttachmentLookupField, FetchAttachmentRequestBuilder, MailinatorClient, MessageApi,
+    ///    LookupField, FetchAttachmentRequestBuilder, Mailinator, ApiMessageEndpoints,
     ///};
     ///
     ///#[tokio::main]
@@ -361,9 +666,12 @@ pub trait ApiMessageEndpoints {
     ///    let domain = String::from("example.com");
     ///    let inbox = String::from("testinbox");
     ///    let msg_id = String::from("msg_id");
-    ///    let attachment_id = AttachmentLookupField::Id(1);
+    ///    let attachment_id = LookupField::Id(1);
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = FetchAttachmentRequestBuilder::default()
     ///        .domain(domain)
@@ -375,27 +683,71 @@ pub trait ApiMessageEndpoints {
     ///
     ///    match client.fetch_attachment(request).await {
     ///        Err(e) => println!("Error: {e}"),
-    ///        Ok(msg) => println!("{msg:?}"),
+    ///        Ok(file) => {
+    ///            let name = file.filename.clone().unwrap_or_else(|| String::from("attachment"));
+    ///            file.save_to(name).await.expect("failed to save attachment");
+    ///        }
     ///    }
     ///}
     ///```
     async fn fetch_attachment(
         &self,
         request: FetchAttachmentRequest,
-    ) -> Result<Email, Report>;
+    ) -> Result<AttachmentFile, Error>;
+    /// Download Attachment
+    ///
+    /// Streams the content of an attachment into `writer` without buffering it, for large files.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{
+    ///    LookupField, FetchAttachmentRequestBuilder, Mailinator, ApiMessageEndpoints,
+    ///};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = FetchAttachmentRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(String::from("testinbox"))
+    ///        .message_id(String::from("msg_id"))
+    ///        .attachment(LookupField::Name(String::from("report.pdf")))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    let mut file = tokio::fs::File::create("report.pdf")
+    ///        .await
+    ///        .expect("failed to create file");
+    ///    match client.download_attachment(request, &mut file).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(info) => println!("{info:?}"),
+    ///    }
+    ///}
+    ///```
+    async fn download_attachment(
+        &self,
+        request: FetchAttachmentRequest,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<AttachmentInfo, Error>;
     /// Delete ALL Messages (by Domain)
     ///
     /// This endpoint deletes ALL messages from a Private Domain. Caution: This action is irreversible.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{DeleteAllDomainMessageRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{DeleteAllDomainMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
     ///    let team_api_token = String::from("XXXXX-XXXXXX");
     ///    let domain = String::from("example.com");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = DeleteAllDomainMessageRequestBuilder::default()
     ///        .domain(domain)
@@ -411,13 +763,13 @@ pub trait ApiMessageEndpoints {
     async fn delete_all_domain_messages(
         &self,
         request: DeleteAllDomainMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report>;
+    ) -> Result<DeleteMessageResponse, Error>;
     /// Delete ALL Messages (by Domain)
     ///
     /// This endpoint deletes ALL messages from a Private Domain. Caution: This action is irreversible.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{DeleteAllInboxMessageRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{DeleteAllInboxMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
@@ -425,7 +777,10 @@ pub trait ApiMessageEndpoints {
     ///    let domain = String::from("example.com");
     ///    let inbox = String::from("testinbox");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = DeleteAllInboxMessageRequestBuilder::default()
     ///        .domain(domain)
@@ -442,13 +797,13 @@ pub trait ApiMessageEndpoints {
     async fn delete_all_inbox_messages(
         &self,
         request: DeleteAllInboxMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report>;
+    ) -> Result<DeleteMessageResponse, Error>;
     /// Delete ALL Messages (by Inbox)
     ///
     /// This endpoint deletes ALL messages from a specific private inbox.
     ///
-    /// ```rust
-    ///use mailinator_rs::prelude::{DeleteMessageRequestBuilder, MailinatorClient, MessageApi};
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{DeleteMessageRequestBuilder, Mailinator, ApiMessageEndpoints};
     ///
     ///#[tokio::main]
     ///async fn main() {
@@ -457,7 +812,10 @@ pub trait ApiMessageEndpoints {
     ///    let inbox = String::from("testinbox");
     ///    let msg_id = String::from("msg_id");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = DeleteMessageRequestBuilder::default()
     ///        .domain(domain)
@@ -475,7 +833,7 @@ pub trait ApiMessageEndpoints {
     async fn delete_message(
         &self,
         request: DeleteMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report>;
+    ) -> Result<DeleteMessageResponse, Error>;
     /// Inject a Message (HTTP Post messages)
     ///
     /// This endpoint allows you to deliver a JSON message into your private domain. This is similar to simply emailing a message
@@ -484,9 +842,9 @@ pub trait ApiMessageEndpoints {
     /// Note that injected JSON Messages can have any schema they choose. However, if you want the Web interface to display
     /// them, they must follow a general email format with the fields of From, Subject, and Parts (see "Fetch Message" above).
     ///
-    /// ```rust
+    /// ```rust,no_run
     ///use mailinator_rs::prelude::{
-    ///    InjectMessageRequestBuilder, MailinatorClient, MessageApi, NewEmailMessageBuilder,
+    ///    InjectMessageRequestBuilder, Mailinator, ApiMessageEndpoints, NewEmailBuilder,
     ///};
     ///
     ///#[tokio::main]
@@ -495,7 +853,10 @@ pub trait ApiMessageEndpoints {
     ///    let domain = String::from("example.com");
     ///    let inbox = String::from("testinbox");
     ///
-    ///    let client = MailinatorClient::new(None, Some(team_api_token));
+    ///    let client = Mailinator::builder()
+    ///        .api_token(team_api_token)
+    ///        .build()
+    ///        .expect("failed to build client");
     ///
     ///    let request = InjectMessageRequestBuilder::default()
     ///        .domain(domain)
@@ -503,7 +864,7 @@ pub trait ApiMessageEndpoints {
     ///        .build()
     ///        .expect("failed to build request");
     ///
-    ///    let email = NewEmailMessageBuilder::default()
+    ///    let email = NewEmailBuilder::default()
     ///        .subject(String::from("Hello world"))
     ///        .text(Some(String::from("An example email.")))
     ///        .from(String::from("sender@example.com"))
@@ -520,7 +881,7 @@ pub trait ApiMessageEndpoints {
         &self,
         request: InjectMessageRequest,
         email: NewEmail,
-    ) -> Result<InjectMessageResponse, Report>;
+    ) -> Result<InjectMessageResponse, Error>;
 }
 
 #[async_trait]
@@ -528,63 +889,110 @@ impl ApiMessageEndpoints for Mailinator {
     async fn fetch_inbox(
         &self,
         request: FetchInboxRequest,
-    ) -> Result<FetchInboxResponse, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<FetchInboxResponse, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn fetch_message(
         &self,
         request: FetchMessageRequest,
-    ) -> Result<Email, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<Email, Error> {
+        self.get(request.as_url_path()?).await
+    }
+    async fn fetch_latest_messages(
+        &self,
+        request: FetchLatestMessagesRequest,
+    ) -> Result<FetchLatestMessagesResponse, Error> {
+        self.get(request.as_url_path()?).await
+    }
+
+    async fn fetch_raw_message(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<RawMessage, Error> {
+        let path =
+            format!("{}/raw", request.as_url_path()?);
+        let resp = self.get_response(path).await?;
+        Ok(RawMessage {
+            data: resp.bytes().await?.to_vec(),
+        })
+    }
+    async fn fetch_smtp_log(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<SmtpLogResponse, Error> {
+        self.get(format!(
+            "{}/smtplog",
+            request.as_url_path()?
+        ))
+        .await
+    }
+
+    async fn fetch_sms_inbox(
+        &self,
+        request: FetchSmsInboxRequest,
+    ) -> Result<SmsInbox, Error> {
+        let number = request.number();
+        let resp = self.get(request.as_url_path()?).await?;
+        Ok(SmsInbox::from_response(resp, number))
     }
     async fn fetch_sms_message(
         &self,
         request: FetchSmsMessageRequest,
-    ) -> Result<Email, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<SmsMessage, Error> {
+        let number = request.number();
+        let email =
+            self.get(request.as_url_path()?).await?;
+        Ok(SmsMessage::from_email(email, &number))
     }
+
     async fn fetch_list_of_attachments(
         &self,
         request: FetchListOfAttachmentRequest,
-    ) -> Result<FetchListOfAttachmentResponse, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<FetchListOfAttachmentResponse, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn fetch_links(
         &self,
         request: FetchLinkRequest,
-    ) -> Result<FetchLinkResponse, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<FetchLinkResponse, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn fetch_attachment(
         &self,
         request: FetchAttachmentRequest,
-    ) -> Result<Email, Report> {
-        // TODO: implement a trait helper for file extraction
-        self.get(request.as_url_path()).await
+    ) -> Result<AttachmentFile, Error> {
+        self.download_attachment_bytes(request).await
+    }
+    async fn download_attachment(
+        &self,
+        request: FetchAttachmentRequest,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<AttachmentInfo, Error> {
+        self.download_attachment_into(request, writer).await
     }
     async fn delete_all_domain_messages(
         &self,
         request: DeleteAllDomainMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report> {
-        self.delete(request.as_url_path()).await
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.delete(request.as_url_path()?).await
     }
     async fn delete_all_inbox_messages(
         &self,
         request: DeleteAllInboxMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report> {
-        self.delete(request.as_url_path()).await
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.delete(request.as_url_path()?).await
     }
     async fn delete_message(
         &self,
         request: DeleteMessageRequest,
-    ) -> Result<DeleteMessageResponse, Report> {
-        self.delete(request.as_url_path()).await
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.delete(request.as_url_path()?).await
     }
     async fn inject_message(
         &self,
         request: InjectMessageRequest,
         email: NewEmail,
-    ) -> Result<InjectMessageResponse, Report> {
-        self.post_json(request.as_url_path(), email).await
+    ) -> Result<InjectMessageResponse, Error> {
+        self.post_json(request.as_url_path()?, email).await
     }
 }
@@ -0,0 +1,292 @@
+use super::Email;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::cmp::Reverse;
+
+static NUMERIC: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b\d{4,8}\b").expect("valid regex")
+});
+static ALPHANUMERIC: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[A-Za-z0-9]{4,10}\b")
+        .expect("valid regex")
+});
+static SCRIPT_OR_STYLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?is)<script\b.*?</script>|<style\b.*?</style>",
+    )
+    .expect("valid regex")
+});
+static TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<[^>]*>").expect("valid regex")
+});
+static ENTITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"&(#[xX]?[0-9A-Fa-f]+|[A-Za-z]+);")
+        .expect("valid regex")
+});
+
+const KEYWORDS: &[&str] = &[
+    "code",
+    "otp",
+    "verification",
+    "verify",
+    "passcode",
+    "password",
+    "pin",
+    "one-time",
+    "one time",
+    "token",
+    "confirm",
+];
+
+/// Characters before a candidate searched for a keyword.
+const KEYWORD_WINDOW: usize = 60;
+
+/// Where a code candidate was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeSource {
+    Subject,
+    /// A plain text part, by index in [`Email::parts`].
+    Text(usize),
+    /// An html part with tags stripped, by index in
+    /// [`Email::parts`].
+    Html(usize),
+    /// The text of an SMS message.
+    Sms,
+}
+
+/// A possible verification code, higher scores rank first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeCandidate {
+    pub code: String,
+    pub source: CodeSource,
+    pub score: u32,
+}
+
+/// Finds one-time passwords and verification codes in an email.
+///
+/// Built-in heuristics favour numeric or mixed alphanumeric tokens
+/// close to words like "code", "OTP" or "verification". Custom
+/// patterns always rank above them; their first capture group is
+/// used as the code when present.
+///
+/// ```rust
+/// use mailinator_rs::prelude::CodeExtractor;
+/// use regex::Regex;
+///
+/// let extractor = CodeExtractor::default()
+///     .with_pattern(Regex::new(r"ACME-(\d{6})").unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CodeExtractor {
+    patterns: Vec<Regex>,
+}
+
+impl CodeExtractor {
+    #[must_use]
+    pub fn with_pattern(mut self, pattern: Regex) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Every candidate found in the subject, text and html parts,
+    /// best first and without duplicates.
+    #[must_use]
+    pub fn extract(
+        &self,
+        email: &Email,
+    ) -> Vec<CodeCandidate> {
+        let mut sources = Vec::new();
+        if let Some(subject) = &email.subject {
+            sources.push((
+                CodeSource::Subject,
+                subject.clone(),
+            ));
+        }
+        for (idx, part) in email.parts.iter().enumerate() {
+            let html =
+                match part.content_type().value.as_str() {
+                    "text/html" => true,
+                    "text/plain" => false,
+                    _ => continue,
+                };
+            let Some(body) = part.decoded_text() else {
+                continue;
+            };
+            if html {
+                sources.push((
+                    CodeSource::Html(idx),
+                    strip_html(&body),
+                ));
+            } else {
+                sources.push((CodeSource::Text(idx), body));
+            }
+        }
+
+        let mut candidates: Vec<CodeCandidate> = Vec::new();
+        for (source, text) in &sources {
+            for candidate in
+                self.extract_text(text, *source)
+            {
+                match candidates
+                    .iter_mut()
+                    .find(|c| c.code == candidate.code)
+                {
+                    Some(known)
+                        if known.score
+                            < candidate.score =>
+                    {
+                        *known = candidate;
+                    }
+                    Some(_) => {}
+                    None => candidates.push(candidate),
+                }
+            }
+        }
+        candidates.sort_by_key(|c| Reverse(c.score));
+        candidates
+    }
+
+    /// Candidates found in a single piece of text.
+    #[must_use]
+    pub fn extract_text(
+        &self,
+        text: &str,
+        source: CodeSource,
+    ) -> Vec<CodeCandidate> {
+        let mut found = Vec::new();
+        for pattern in &self.patterns {
+            for caps in pattern.captures_iter(text) {
+                let code =
+                    caps.get(1).or_else(|| caps.get(0));
+                if let Some(code) = code {
+                    found.push(CodeCandidate {
+                        code: code.as_str().to_owned(),
+                        source,
+                        score: 100,
+                    });
+                }
+            }
+        }
+
+        for m in NUMERIC
+            .find_iter(text)
+            .chain(ALPHANUMERIC.find_iter(text))
+        {
+            let code = m.as_str();
+            let Some(mut score) = base_score(code) else {
+                continue;
+            };
+            if keyword_before(text, m.start()) {
+                score += 10;
+            }
+            if !found
+                .iter()
+                .any(|c: &CodeCandidate| c.code == code)
+            {
+                found.push(CodeCandidate {
+                    code: code.to_owned(),
+                    source,
+                    score,
+                });
+            }
+        }
+        found
+    }
+}
+
+/// Scores a token on its shape alone, `None` if it does not look
+/// like a code at all.
+fn base_score(code: &str) -> Option<u32> {
+    let digits =
+        code.chars().filter(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    if digits == code.len() {
+        let looks_like_year = code.len() == 4
+            && (code.starts_with("19")
+                || code.starts_with("20"));
+        return Some(match code.len() {
+            _ if looks_like_year => 1,
+            6 => 5,
+            4 | 5 | 7 | 8 => 3,
+            _ => 1,
+        });
+    }
+    // Mixed tokens are only codes when upper case or fully lower
+    // case, not ordinary words with a digit.
+    let upper =
+        code.chars().all(|c| !c.is_ascii_lowercase());
+    Some(if upper { 2 } else { 1 })
+}
+
+/// Whether a keyword precedes `end`, a char boundary of `text`.
+fn keyword_before(text: &str, end: usize) -> bool {
+    let mut start = end.saturating_sub(KEYWORD_WINDOW);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let window = text[start..end].to_ascii_lowercase();
+    KEYWORDS.iter().any(|k| window.contains(k))
+}
+
+/// Turns html into plain text, dropping scripts, styles and tags
+/// and decoding common entities.
+pub fn strip_html(html: &str) -> String {
+    let text = SCRIPT_OR_STYLE.replace_all(html, " ");
+    let text = TAG.replace_all(&text, " ");
+    decode_entities(&text)
+}
+
+/// Decodes named, decimal and hexadecimal html entities, leaving
+/// unknown ones as they are.
+pub fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            let entity = &caps[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| {
+                        u32::from_str_radix(hex, 16).ok()
+                    })
+                    .or_else(|| {
+                        entity
+                            .strip_prefix('#')?
+                            .parse()
+                            .ok()
+                    })
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(
+                || caps[0].to_owned(),
+                String::from,
+            )
+        })
+        .into_owned()
+}
+
+impl Email {
+    /// Verification code candidates found with the built-in
+    /// heuristics, best first.
+    #[must_use]
+    pub fn verification_codes(&self) -> Vec<CodeCandidate> {
+        CodeExtractor::default().extract(self)
+    }
+
+    /// The best verification code candidate, if any.
+    #[must_use]
+    pub fn verification_code(&self) -> Option<String> {
+        self.verification_codes()
+            .into_iter()
+            .next()
+            .map(|c| c.code)
+    }
+}
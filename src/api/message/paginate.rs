@@ -0,0 +1,229 @@
+use super::{
+    inbox::{
+        within, FetchInboxRequestBuilder,
+        FetchInboxRequestQueryParamsBuilder, InboxSelector,
+        Msg, SelectorInput, Sorting,
+    },
+    ApiMessageEndpoints,
+};
+use crate::client::Mailinator;
+use crate::names::{DomainName, NameInput};
+use crate::Error;
+use chrono::{DateTime, Utc};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use std::collections::HashSet;
+
+/// Walks every page of the selected inboxes, every inbox of the
+/// domain by default.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(
+    validate = "Self::validate",
+    error = "crate::Error"
+))]
+pub struct PaginateInboxRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "SelectorInput",
+            build = "self.inbox.optional()?.unwrap_or_default()"
+        )
+    )]
+    inbox: InboxSelector,
+    #[builder(default = "100")]
+    page_size: usize,
+    #[builder(default = "Sorting::Descending")]
+    sort: Sorting,
+    #[builder(default)]
+    decode_subject: Option<bool>,
+    /// Only yield messages received at or after this time.
+    #[builder(default)]
+    since: Option<DateTime<Utc>>,
+    /// Only yield messages received before this time.
+    #[builder(default)]
+    until: Option<DateTime<Utc>>,
+}
+
+impl PaginateInboxRequestBuilder {
+    fn validate(&self) -> Result<(), Error> {
+        if self.page_size == Some(0) {
+            return Err(Error::Builder(String::from(
+                "invalid paginate inbox request: page_size must not be zero",
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Lazily fetches inbox pages until a short page marks the end.
+///
+/// Messages shifting between pages while walking them are only
+/// yielded once. With a time range, paging stops as soon as a page
+/// goes past the range in the requested order.
+#[derive(Debug, Clone)]
+pub struct InboxPaginator {
+    client: Mailinator,
+    request: PaginateInboxRequest,
+}
+
+struct PageState {
+    paginator: InboxPaginator,
+    skip: usize,
+    done: bool,
+    seen: HashSet<String>,
+}
+
+impl InboxPaginator {
+    /// Every message, one page request at a time.
+    #[must_use]
+    pub fn into_stream(
+        self,
+    ) -> BoxStream<'static, Result<Msg, Error>> {
+        let state = PageState {
+            paginator: self,
+            skip: 0,
+            done: false,
+            seen: HashSet::new(),
+        };
+        stream::try_unfold(state, |mut state| async move {
+            if state.done {
+                return Ok::<_, Error>(None);
+            }
+            let page = state
+                .paginator
+                .fetch_page(state.skip)
+                .await?;
+            let PaginateInboxRequest {
+                page_size,
+                sort,
+                since,
+                until,
+                ..
+            } = state.paginator.request.clone();
+            let past_range = page.last().is_some_and(|m| {
+                m.received_at().is_some_and(|at| match sort
+                {
+                    Sorting::Descending => {
+                        since.is_some_and(|s| at < s)
+                    }
+                    Sorting::Ascending => {
+                        until.is_some_and(|u| at >= u)
+                    }
+                })
+            });
+            state.done = page.is_empty()
+                || page.len() < page_size
+                || past_range;
+            state.skip += page.len();
+            let page = page
+                .into_iter()
+                .filter(|m| {
+                    within(m.received_at(), since, until)
+                })
+                .filter(|m| {
+                    m.id.as_ref().map_or(true, |id| {
+                        state.seen.insert(id.clone())
+                    })
+                })
+                .collect::<Vec<_>>();
+            Ok(Some((page, state)))
+        })
+        .map_ok(|page| {
+            stream::iter(page.into_iter().map(Ok))
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Fetches every page and returns all messages.
+    ///
+    /// # Errors
+    /// Returns the first error raised while fetching a page.
+    pub async fn collect_all(
+        self,
+    ) -> Result<Vec<Msg>, Error> {
+        self.into_stream().try_collect().await
+    }
+
+    async fn fetch_page(
+        &self,
+        skip: usize,
+    ) -> Result<Vec<Msg>, Error> {
+        let PaginateInboxRequest {
+            domain,
+            inbox,
+            page_size,
+            sort,
+            decode_subject,
+            ..
+        } = &self.request;
+
+        let page = self
+            .client
+            .fetch_inbox(
+                FetchInboxRequestBuilder::default()
+                    .domain(domain.clone())
+                    .inbox(inbox.clone())
+                    .query_params(Some(
+                        FetchInboxRequestQueryParamsBuilder::default()
+                            .skip(Some(skip))
+                            .limit(Some(*page_size))
+                            .sort(Some(sort.clone()))
+                            .decode_subject(*decode_subject)
+                            .build()?,
+                    ))
+                    .build()?,
+            )
+            .await?;
+        Ok(page.msgs)
+    }
+}
+
+impl Mailinator {
+    /// Pages through an inbox without truncating at the server's
+    /// default limit.
+    ///
+    /// ```rust,no_run
+    ///use futures::TryStreamExt;
+    ///use mailinator_rs::prelude::{Mailinator, PaginateInboxRequestBuilder};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = PaginateInboxRequestBuilder::default()
+    ///        .domain(String::from("private"))
+    ///        .page_size(50usize)
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    let mut msgs = client.paginate_inbox(request).into_stream();
+    ///    while let Ok(Some(msg)) = msgs.try_next().await {
+    ///        println!("{msg:?}");
+    ///    }
+    ///}
+    ///```
+    #[must_use]
+    pub fn paginate_inbox(
+        &self,
+        request: PaginateInboxRequest,
+    ) -> InboxPaginator {
+        InboxPaginator {
+            client: self.clone(),
+            request,
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use super::{
+    inbox::{from_millis, FetchInboxResponse, Msg},
+    otp::{CodeCandidate, CodeExtractor, CodeSource},
+    Email,
+};
+use crate::names::{DomainName, MessageId, NameInput};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+use std::{cmp::Reverse, fmt, str::FromStr};
+
+/// A phone number in E.164 form, e.g. `+14155550123`.
+///
+/// Spaces, dashes, dots and parentheses are ignored when parsing,
+/// and the leading `+` is optional. Mailinator names the SMS inbox
+/// of a number after its digits.
+///
+/// ```rust
+/// use mailinator_rs::prelude::SmsNumber;
+///
+/// let number: SmsNumber = "+44 20 7946 0018".parse().unwrap();
+/// assert_eq!(number.to_string(), "+442079460018");
+/// assert_eq!(number.digits(), "442079460018");
+/// assert!("0044207946".parse::<SmsNumber>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SmsNumber(String);
+
+impl SmsNumber {
+    /// The number without the leading `+`.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for SmsNumber {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            Err(Error::Builder(format!(
+                "invalid E.164 number {s:?}: {reason}"
+            )))
+        };
+        let trimmed = s.trim();
+        let rest =
+            trimmed.strip_prefix('+').unwrap_or(trimmed);
+        let mut digits = String::with_capacity(rest.len());
+        for c in rest.chars() {
+            match c {
+                '0'..='9' => digits.push(c),
+                ' ' | '-' | '.' | '(' | ')' => {}
+                _ => {
+                    return invalid("unexpected character")
+                }
+            }
+        }
+        if digits.starts_with('0') {
+            return invalid(
+                "country code can not start with 0",
+            );
+        }
+        if !(2..=15).contains(&digits.len()) {
+            return invalid("expected 2 to 15 digits");
+        }
+        Ok(Self(digits))
+    }
+}
+
+impl TryFrom<&str> for SmsNumber {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for SmsNumber {
+    type Error = Error;
+
+    fn try_from(
+        value: String,
+    ) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for SmsNumber {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "+{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SmsNumber {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct FetchSmsInboxRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    sms_number: SmsNumber,
+}
+
+impl FetchSmsInboxRequest {
+    pub(crate) fn number(&self) -> SmsNumber {
+        self.sms_number.clone()
+    }
+}
+
+impl AsUrl for FetchSmsInboxRequest {
+    fn as_url_path(self) -> Result<String, Error> {
+        let Self { domain, sms_number } = self;
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(sms_number.digits())
+            .build())
+    }
+}
+
+/// Messages received by a phone number, newest first.
+#[derive(Debug, Clone)]
+pub struct SmsInbox {
+    pub domain: String,
+    pub number: SmsNumber,
+    pub msgs: Vec<SmsMessage>,
+}
+
+impl SmsInbox {
+    pub(crate) fn from_response(
+        resp: FetchInboxResponse,
+        number: SmsNumber,
+    ) -> Self {
+        let msgs = resp
+            .msgs
+            .into_iter()
+            .map(|m| SmsMessage::from_summary(m, &number))
+            .collect();
+        Self {
+            domain: resp.domain,
+            number,
+            msgs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct FetchSmsMessageRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    sms_number: SmsNumber,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.message_id.required(\"message_id\")?"
+        )
+    )]
+    message_id: MessageId,
+}
+
+impl FetchSmsMessageRequest {
+    pub(crate) fn number(&self) -> SmsNumber {
+        self.sms_number.clone()
+    }
+}
+
+impl AsUrl for FetchSmsMessageRequest {
+    fn as_url_path(self) -> Result<String, Error> {
+        let Self {
+            domain,
+            sms_number,
+            message_id,
+        } = self;
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain)
+            .segment("inboxes")
+            .segment(sms_number.digits())
+            .segment("messages")
+            .segment(message_id)
+            .build())
+    }
+}
+
+/// A text message received by an SMS inbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsMessage {
+    pub id: Option<String>,
+    /// The sender as reported by the carrier, a phone number or a
+    /// short code.
+    pub from: String,
+    pub to: SmsNumber,
+    pub text: String,
+    pub received_at: Option<DateTime<Utc>>,
+}
+
+impl SmsMessage {
+    /// Builds a message from an inbox summary, whose subject holds
+    /// the text.
+    fn from_summary(msg: Msg, to: &SmsNumber) -> Self {
+        Self {
+            id: msg.id,
+            from: msg.from.unwrap_or_default(),
+            to: parse_or(msg.to.as_deref(), to),
+            text: msg.subject.unwrap_or_default(),
+            received_at: from_millis(msg.time),
+        }
+    }
+
+    /// Builds a message from a fetched message, reading the text
+    /// from its plain text part and falling back to the subject.
+    #[must_use]
+    pub fn from_email(
+        email: Email,
+        to: &SmsNumber,
+    ) -> Self {
+        let text = email
+            .text_body()
+            .map(|b| b.text.trim().to_owned())
+            .or(email.subject)
+            .unwrap_or_default();
+        Self {
+            id: email.id,
+            from: email
+                .from
+                .or(email.fromfull)
+                .unwrap_or_default(),
+            to: parse_or(email.to.as_deref(), to),
+            text,
+            received_at: from_millis(email.time),
+        }
+    }
+
+    /// The sender as an E.164 number, `None` for short codes and
+    /// alphanumeric senders.
+    #[must_use]
+    pub fn from_number(&self) -> Option<SmsNumber> {
+        let number = self.from.parse::<SmsNumber>().ok()?;
+        // Short codes are at most 6 digits long.
+        (number.digits().len() > 6).then_some(number)
+    }
+
+    /// Verification code candidates found in the text, best first.
+    #[must_use]
+    pub fn verification_codes(&self) -> Vec<CodeCandidate> {
+        let mut codes = CodeExtractor::default()
+            .extract_text(&self.text, CodeSource::Sms);
+        codes.sort_by_key(|c| Reverse(c.score));
+
+        codes
+    }
+
+    /// The best verification code candidate, if any.
+    #[must_use]
+    pub fn verification_code(&self) -> Option<String> {
+        self.verification_codes()
+            .into_iter()
+            .next()
+            .map(|c| c.code)
+    }
+}
+
+fn parse_or(
+    value: Option<&str>,
+    fallback: &SmsNumber,
+) -> SmsNumber {
+    value
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| fallback.clone())
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+static IP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b|\b[0-9a-fA-F]{0,4}(?::[0-9a-fA-F]{0,4}){2,7}\b")
+        .expect("valid regex")
+});
+
+/// How Mailinator received a message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpLogResponse {
+    pub log: Vec<SmtpLogEvent>,
+}
+
+/// A line of the smtp transaction log.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpLogEvent {
+    #[serde(alias = "event", alias = "message")]
+    pub log: String,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_time"
+    )]
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// The smtp step an event describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmtpEventKind {
+    /// Connection from the sending server.
+    Connect {
+        ip: Option<String>,
+    },
+    /// `HELO` or `EHLO` with the announced host name.
+    Helo(String),
+    MailFrom(String),
+    RcptTo(String),
+    Data,
+    Quit,
+    Other,
+}
+
+impl SmtpLogEvent {
+    #[must_use]
+    pub fn kind(&self) -> SmtpEventKind {
+        let text = self.log.trim();
+        let lower = text.to_ascii_lowercase();
+        let argument = |keyword: &str| {
+            lower.find(keyword).map(|idx| {
+                text[idx + keyword.len()..]
+                    .trim()
+                    .trim_start_matches(':')
+                    .trim()
+                    .trim_start_matches('<')
+                    .split(['>', ' '])
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned()
+            })
+        };
+
+        argument("mail from")
+            .map(SmtpEventKind::MailFrom)
+            .or_else(|| {
+                argument("rcpt to")
+                    .map(SmtpEventKind::RcptTo)
+            })
+            .or_else(|| {
+                argument("helo")
+                    .or_else(|| argument("ehlo"))
+                    .map(SmtpEventKind::Helo)
+            })
+            .unwrap_or_else(|| {
+                if lower.contains("connect") {
+                    SmtpEventKind::Connect {
+                        ip: IP
+                            .find(text)
+                            .map(|m| m.as_str().to_owned()),
+                    }
+                } else if lower.starts_with("data")
+                    || lower.contains(" data")
+                {
+                    SmtpEventKind::Data
+                } else if lower.contains("quit") {
+                    SmtpEventKind::Quit
+                } else {
+                    SmtpEventKind::Other
+                }
+            })
+    }
+}
+
+impl SmtpLogResponse {
+    /// Ip address of the server that delivered the message.
+    #[must_use]
+    pub fn connecting_ip(&self) -> Option<String> {
+        self.kinds().find_map(|kind| match kind {
+            SmtpEventKind::Connect { ip } => ip,
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn helo(&self) -> Option<String> {
+        self.kinds().find_map(|kind| match kind {
+            SmtpEventKind::Helo(host) => Some(host),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn mail_from(&self) -> Option<String> {
+        self.kinds().find_map(|kind| match kind {
+            SmtpEventKind::MailFrom(from) => Some(from),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn rcpt_to(&self) -> Vec<String> {
+        self.kinds()
+            .filter_map(|kind| match kind {
+                SmtpEventKind::RcptTo(to) => Some(to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Time between the first and the last timestamped events.
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        let mut times =
+            self.log.iter().filter_map(|e| e.time);
+        let first = times.next()?;
+        let last = times.next_back().unwrap_or(first);
+        (last - first).to_std().ok()
+    }
+
+    fn kinds(
+        &self,
+    ) -> impl Iterator<Item = SmtpEventKind> + '_ {
+        self.log.iter().map(SmtpLogEvent::kind)
+    }
+}
+
+/// Accepts rfc 3339 strings as well as epoch milliseconds.
+fn deserialize_time<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Time {
+        Millis(i64),
+        Text(String),
+    }
+
+    Ok(match Option::<Time>::deserialize(deserializer)? {
+        Some(Time::Millis(ms)) => {
+            DateTime::from_timestamp_millis(ms)
+        }
+        Some(Time::Text(text)) => {
+            DateTime::parse_from_rfc3339(&text)
+                .map(|t| t.with_timezone(&Utc))
+                .ok()
+        }
+        None => None,
+    })
+}
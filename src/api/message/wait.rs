@@ -0,0 +1,354 @@
+use super::{
+    inbox::{
+        FetchInboxRequestBuilder,
+        FetchInboxRequestQueryParamsBuilder, InboxSelector,
+        Msg, SelectorInput, Sorting,
+    },
+    ApiMessageEndpoints, Email, FetchMessageRequestBuilder,
+};
+use crate::client::Mailinator;
+use crate::names::{DomainName, NameInput};
+use crate::Error;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::{
+    collections::HashSet, fmt, sync::Arc, time::Duration,
+};
+use tokio::time::Instant;
+
+type Predicate = Arc<dyn Fn(&Msg) -> bool + Send + Sync>;
+
+/// Predicate over message summaries, every condition set must hold.
+///
+/// ```rust
+/// use mailinator_rs::prelude::MessageFilter;
+///
+/// let filter = MessageFilter::default()
+///     .subject_contains("Confirm your account")
+///     .from("noreply@example.com");
+/// ```
+#[derive(Clone, Default)]
+pub struct MessageFilter {
+    subject_contains: Option<String>,
+    subject_regex: Option<Regex>,
+    from: Option<String>,
+    to: Option<String>,
+    newer_than: Option<DateTime<Utc>>,
+    older_than: Option<DateTime<Utc>>,
+    custom: Option<Predicate>,
+}
+
+impl MessageFilter {
+    /// Subject contains `text`, ignoring case.
+    #[must_use]
+    pub fn subject_contains(
+        mut self,
+        text: impl Into<String>,
+    ) -> Self {
+        self.subject_contains =
+            Some(text.into().to_lowercase());
+        self
+    }
+
+    #[must_use]
+    pub fn subject_matches(mut self, regex: Regex) -> Self {
+        self.subject_regex = Some(regex);
+        self
+    }
+
+    /// Sender contains `from`, ignoring case.
+    #[must_use]
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into().to_lowercase());
+        self
+    }
+
+    /// Recipient contains `to`, ignoring case.
+    #[must_use]
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into().to_lowercase());
+        self
+    }
+
+    /// Message was received strictly after `time`.
+    #[must_use]
+    pub const fn newer_than(
+        mut self,
+        time: DateTime<Utc>,
+    ) -> Self {
+        self.newer_than = Some(time);
+        self
+    }
+
+    /// Message was received strictly before `time`.
+    #[must_use]
+    pub const fn older_than(
+        mut self,
+        time: DateTime<Utc>,
+    ) -> Self {
+        self.older_than = Some(time);
+        self
+    }
+
+    /// Any additional condition.
+    #[must_use]
+    pub fn matching<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Msg) -> bool + Send + Sync + 'static,
+    {
+        self.custom = Some(Arc::new(predicate));
+        self
+    }
+
+    #[must_use]
+    pub fn matches(&self, msg: &Msg) -> bool {
+        fn contains(
+            field: Option<&String>,
+            text: &str,
+        ) -> bool {
+            field.is_some_and(|f| {
+                f.to_lowercase().contains(text)
+            })
+        }
+
+        self.subject_contains.as_deref().map_or(true, |t| {
+            contains(msg.subject.as_ref(), t)
+        }) && self.subject_regex.as_ref().map_or(
+            true,
+            |re| {
+                msg.subject
+                    .as_deref()
+                    .is_some_and(|s| re.is_match(s))
+            },
+        ) && self.from.as_deref().map_or(true, |t| {
+            contains(msg.from.as_ref(), t)
+        }) && self
+            .to
+            .as_deref()
+            .map_or(true, |t| contains(msg.to.as_ref(), t))
+            && self.newer_than.map_or(true, |since| {
+                msg.received_at()
+                    .is_some_and(|at| at > since)
+            })
+            && self.older_than.map_or(true, |until| {
+                msg.received_at()
+                    .is_some_and(|at| at < until)
+            })
+            && self.custom.as_ref().map_or(true, |p| p(msg))
+    }
+}
+
+impl fmt::Debug for MessageFilter {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("MessageFilter")
+            .field(
+                "subject_contains",
+                &self.subject_contains,
+            )
+            .field("subject_regex", &self.subject_regex)
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("newer_than", &self.newer_than)
+            .field("older_than", &self.older_than)
+            .field("custom", &self.custom.is_some())
+            .finish()
+    }
+}
+
+/// Polls an inbox until a message matching `filter` arrives.
+///
+/// The poll interval starts at `poll_interval` and grows by the
+/// `backoff` factor, at least 1, up to `max_poll_interval`.
+#[derive(Debug, Builder)]
+#[builder(build_fn(
+    validate = "Self::validate",
+    error = "crate::Error"
+))]
+pub struct AwaitMessageRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "SelectorInput",
+            build = "self.inbox.required(\"inbox\")?"
+        )
+    )]
+    inbox: InboxSelector,
+    #[builder(default)]
+    filter: MessageFilter,
+    #[builder(default = "DEFAULT_POLL_INTERVAL")]
+    poll_interval: Duration,
+    #[builder(default = "DEFAULT_MAX_POLL_INTERVAL")]
+    max_poll_interval: Duration,
+    #[builder(default = "1.5")]
+    backoff: f64,
+    #[builder(default = "Duration::from_secs(60)")]
+    timeout: Duration,
+}
+
+const DEFAULT_POLL_INTERVAL: Duration =
+    Duration::from_secs(2);
+const DEFAULT_MAX_POLL_INTERVAL: Duration =
+    Duration::from_secs(15);
+
+impl AwaitMessageRequestBuilder {
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |reason: &str| {
+            Err(Error::Builder(format!(
+                "invalid await message request: {reason}"
+            )))
+        };
+        if self
+            .backoff
+            .is_some_and(|b| !b.is_finite() || b < 1.0)
+        {
+            return invalid(
+                "backoff must be finite and at least 1",
+            );
+        }
+        let poll = self
+            .poll_interval
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        if poll.is_zero() {
+            return invalid(
+                "poll_interval must not be zero",
+            );
+        }
+        if self
+            .max_poll_interval
+            .unwrap_or(DEFAULT_MAX_POLL_INTERVAL)
+            < poll
+        {
+            return invalid(
+                "max_poll_interval must not be below poll_interval",
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Mailinator {
+    /// Waits for a message matching the request filter and fetches
+    /// it in full.
+    ///
+    /// ```rust,no_run
+    ///use mailinator_rs::prelude::{
+    ///    AwaitMessageRequestBuilder, Mailinator, MessageFilter,
+    ///};
+    ///use std::time::Duration;
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = AwaitMessageRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .inbox(String::from("testinbox"))
+    ///        .filter(MessageFilter::default().subject_contains("Welcome"))
+    ///        .timeout(Duration::from_secs(120))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    match client.await_message(request).await {
+    ///        Err(e) => println!("Error: {e}"),
+    ///        Ok(email) => println!("{email:?}"),
+    ///    }
+    ///}
+    ///```
+    ///
+    /// # Errors
+    /// Returns [`Error::Timeout`] listing every message seen over
+    /// all polls, once per id, when none matched before the
+    /// deadline, or any error raised while fetching.
+    pub async fn await_message(
+        &self,
+        request: AwaitMessageRequest,
+    ) -> Result<Email, Error> {
+        let AwaitMessageRequest {
+            domain,
+            inbox,
+            filter,
+            poll_interval,
+            max_poll_interval,
+            backoff,
+            timeout,
+        } = request;
+        let started = Instant::now();
+        let mut interval = poll_interval;
+        let mut seen = Vec::new();
+        let mut seen_ids = HashSet::new();
+
+        loop {
+            let summaries = self
+                .fetch_inbox(
+                    FetchInboxRequestBuilder::default()
+                        .domain(domain.clone())
+                        .inbox(inbox.clone())
+                        .query_params(Some(
+                            FetchInboxRequestQueryParamsBuilder::default()
+                                .sort(Some(Sorting::Descending))
+                                .build()?,
+                        ))
+                        .build()?,
+                )
+                .await?;
+
+            if let Some(id) = summaries
+                .msgs
+                .iter()
+                .find(|m| filter.matches(m))
+                .and_then(|m| m.id.clone())
+            {
+                return self
+                    .fetch_message(
+                        FetchMessageRequestBuilder::default()
+                            .domain(domain)
+                            .inbox(
+                                inbox
+                                    .as_single()
+                                    .map(str::to_owned),
+                            )
+                            .message_id(id)
+                            .build()?,
+                    )
+                    .await;
+            }
+
+            for msg in summaries.msgs {
+                if msg.id.as_ref().map_or(true, |id| {
+                    seen_ids.insert(id.clone())
+                }) {
+                    seen.push(msg);
+                }
+            }
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return Err(Error::Timeout {
+                    elapsed,
+                    seen,
+                });
+            }
+            tokio::time::sleep(
+                interval
+                    .min(timeout.saturating_sub(elapsed)),
+            )
+            .await;
+            interval = interval
+                .mul_f64(backoff)
+                .min(max_poll_interval);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use super::{
+    inbox::{
+        FetchInboxRequestBuilder,
+        FetchInboxRequestQueryParamsBuilder, InboxSelector,
+        Msg, SelectorInput, Sorting,
+    },
+    ApiMessageEndpoints,
+};
+use crate::client::Mailinator;
+use crate::names::{DomainName, NameInput};
+use crate::Error;
+use futures::{
+    stream::{self, AbortHandle, Abortable, BoxStream},
+    Stream, StreamExt,
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Watches the selected inboxes, every inbox of the domain by
+/// default, for new messages.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(
+    validate = "Self::validate",
+    error = "crate::Error"
+))]
+pub struct WatchInboxRequest {
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainName,
+    #[builder(
+        setter(into),
+        field(
+            type = "SelectorInput",
+            build = "self.inbox.optional()?.unwrap_or_default()"
+        )
+    )]
+    inbox: InboxSelector,
+    #[builder(default = "Duration::from_secs(5)")]
+    poll_interval: Duration,
+    /// Number of summaries fetched per page.
+    #[builder(default = "50")]
+    page_size: usize,
+    /// Also yield the newest page of messages already present when
+    /// the watch starts.
+    #[builder(default)]
+    include_existing: bool,
+}
+
+impl WatchInboxRequestBuilder {
+    fn validate(&self) -> Result<(), Error> {
+        if self.page_size == Some(0) {
+            return Err(Error::Builder(String::from(
+                "invalid watch inbox request: page_size must not be zero",
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Stream of new inbox messages, each yielded once, oldest first.
+///
+/// Messages older than the newest one seen are not new, even when
+/// they were never listed before.
+///
+/// Polling stops when the stream is dropped or aborted through its
+/// [`AbortHandle`]. Fetch errors are yielded and polling resumes on
+/// the next interval.
+pub struct InboxWatch {
+    inner:
+        Abortable<BoxStream<'static, Result<Msg, Error>>>,
+    handle: AbortHandle,
+}
+
+impl InboxWatch {
+    /// A handle ending the stream from anywhere, e.g. another task.
+    #[must_use]
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.handle.clone()
+    }
+}
+
+impl Stream for InboxWatch {
+    type Item = Result<Msg, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+struct WatchState {
+    client: Mailinator,
+    request: WatchInboxRequest,
+    seen: HashSet<String>,
+    queue: VecDeque<Msg>,
+    /// Time of the newest message seen, older ones are known even
+    /// when they were never listed.
+    newest: Option<u64>,
+    /// Whether the initial inbox content was recorded.
+    primed: bool,
+    polled: bool,
+}
+
+impl WatchState {
+    /// Pages through the newest messages until a known one, or one
+    /// not newer than the newest seen, shows up and queues the
+    /// unknown ones.
+    ///
+    /// Stopping on time too keeps a poll from walking the whole
+    /// inbox once the seen messages expired.
+    async fn poll(&mut self) -> Result<(), Error> {
+        let WatchInboxRequest {
+            domain,
+            inbox,
+            page_size,
+            include_existing,
+            ..
+        } = &self.request;
+        let mut fresh = Vec::new();
+        let mut fresh_ids = HashSet::new();
+        let mut skip = 0;
+        loop {
+            let page = self
+                .client
+                .fetch_inbox(
+                    FetchInboxRequestBuilder::default()
+                        .domain(domain.clone())
+                        .inbox(inbox.clone())
+                        .query_params(Some(
+                            FetchInboxRequestQueryParamsBuilder::default()
+                                .skip(Some(skip))
+                                .limit(Some(*page_size))
+                                .sort(Some(Sorting::Descending))
+                                .build()?,
+                        ))
+                        .build()?,
+                )
+                .await?
+                .msgs;
+            let last_page =
+                page.is_empty() || page.len() < *page_size;
+            let mut reached_known = false;
+            for msg in page {
+                let Some(id) = msg.id.clone() else {
+                    continue;
+                };
+                let age = self.newest.zip(msg.time).map(
+                    |(newest, time)| time.cmp(&newest),
+                );
+                if self.seen.contains(&id)
+                    || age == Some(Ordering::Less)
+                {
+                    reached_known = true;
+                } else {
+                    reached_known |=
+                        age == Some(Ordering::Equal);
+                    if fresh_ids.insert(id) {
+                        fresh.push(msg);
+                    }
+                }
+            }
+            // Only the newest page makes up the initial content.
+            if !self.primed || reached_known || last_page {
+                break;
+            }
+            skip += page_size;
+        }
+        self.newest = fresh
+            .iter()
+            .filter_map(|m| m.time)
+            .chain(self.newest)
+            .max();
+        self.seen.extend(fresh_ids);
+        if self.primed || *include_existing {
+            self.queue.extend(fresh.into_iter().rev());
+        }
+        self.primed = true;
+        Ok(())
+    }
+}
+
+impl Mailinator {
+    /// Streams new messages as they arrive.
+    ///
+    /// ```rust,no_run
+    ///use futures::StreamExt;
+    ///use mailinator_rs::prelude::{Mailinator, WatchInboxRequestBuilder};
+    ///
+    ///#[tokio::main]
+    ///async fn main() {
+    ///    let client = Mailinator::builder()
+    ///        .api_token("XXXXX-XXXXXX")
+    ///        .build()
+    ///        .expect("failed to build client");
+    ///
+    ///    let request = WatchInboxRequestBuilder::default()
+    ///        .domain(String::from("example.com"))
+    ///        .build()
+    ///        .expect("failed to build request");
+    ///
+    ///    let mut watch = client.watch_inbox(request);
+    ///    while let Some(msg) = watch.next().await {
+    ///        match msg {
+    ///            Err(e) => println!("Error: {e}"),
+    ///            Ok(msg) => println!("{msg:?}"),
+    ///        }
+    ///    }
+    ///}
+    ///```
+    #[must_use]
+    pub fn watch_inbox(
+        &self,
+        request: WatchInboxRequest,
+    ) -> InboxWatch {
+        let state = WatchState {
+            client: self.clone(),
+            request,
+            seen: HashSet::new(),
+            queue: VecDeque::new(),
+            newest: None,
+            primed: false,
+            polled: false,
+        };
+        let stream =
+            stream::unfold(state, |mut state| async move {
+                loop {
+                    if let Some(msg) =
+                        state.queue.pop_front()
+                    {
+                        return Some((Ok(msg), state));
+                    }
+                    if state.polled {
+                        tokio::time::sleep(
+                            state.request.poll_interval,
+                        )
+                        .await;
+                    }
+                    state.polled = true;
+                    if let Err(e) = state.poll().await {
+                        return Some((Err(e), state));
+                    }
+                }
+            });
+        let (inner, handle) =
+            stream::abortable(stream.boxed());
+        InboxWatch { inner, handle }
+    }
+}
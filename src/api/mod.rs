@@ -5,8 +5,7 @@ pub mod message;
 pub mod rules;
 pub mod stats;
 
-
 #[derive(Debug, Deserialize)]
 pub struct ResponseStatus {
     pub status: String,
-}
\ No newline at end of file
+}
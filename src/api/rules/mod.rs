@@ -1,7 +1,9 @@
 use crate::client::Mailinator;
-use crate::path::AsUrl;
+use crate::names::{DomainRef, NameInput};
+use crate::path::{ApiPath, AsUrl};
+
+use crate::Error;
 use async_trait::async_trait;
-use eyre::Report;
 use serde::{Deserialize, Serialize};
 
 use super::ResponseStatus;
@@ -15,12 +17,14 @@ pub enum ConditionOperation {
 }
 
 #[derive(Debug, Deserialize, Builder, Clone, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct ConditionData {
     pub field: String,
     pub value: String,
 }
 
 #[derive(Debug, Deserialize, Builder, Clone, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct Condition {
     pub operation: ConditionOperation,
     pub condition_data: ConditionData,
@@ -35,11 +39,13 @@ pub enum Action {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct WebhookAction {
     pub url: String,
 }
 
 #[derive(Debug, Deserialize, Builder, Clone, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct ConditionAction {
     pub action: Action,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -57,6 +63,7 @@ pub enum ConditionMatch {
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct Rule {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub _id: Option<String>,
@@ -71,72 +78,132 @@ pub struct Rule {
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct CreateRuleRequest {
-    domain: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain.required(\"domain\")?"
+        )
+    )]
+    domain: DomainRef,
 }
 
 impl AsUrl for CreateRuleRequest {
-    fn as_url_path(self) -> String {
-        format!(
-            "/api/v2/domains/{domain_id}/rules/",
-            domain_id = self.domain
-        )
+    fn as_url_path(self) -> Result<String, Error> {
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(self.domain)
+            .segment("rules")
+            .trailing_slash()
+            .build())
     }
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct EnableRuleRequest {
-    domain_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain_id.required(\"domain_id\")?"
+        )
+    )]
+    domain_id: DomainRef,
     rule_id: String,
 }
 
 impl AsUrl for EnableRuleRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self { domain_id, rule_id } = self;
-        format!("/api/v2/domains/{domain_id}/rules/{rule_id}?action=enable")
+        Ok(rule_path(domain_id, rule_id)
+            .query_pair("action", "enable")
+            .build())
     }
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct DisableRuleRequest {
-    domain_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain_id.required(\"domain_id\")?"
+        )
+    )]
+    domain_id: DomainRef,
     rule_id: String,
 }
 
 impl AsUrl for DisableRuleRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self { domain_id, rule_id } = self;
-        format!("/api/v2/domains/{domain_id}/rules/{rule_id}?action=disable")
+        Ok(rule_path(domain_id, rule_id)
+            .query_pair("action", "disable")
+            .build())
     }
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct ListRulesRequest {
-    domain_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain_id.required(\"domain_id\")?"
+        )
+    )]
+    domain_id: DomainRef,
 }
 
 impl AsUrl for ListRulesRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self { domain_id } = self;
-        format!("/api/v2/domains/{domain_id}/rules/")
+        Ok(ApiPath::v2()
+            .segment("domains")
+            .segment(domain_id)
+            .segment("rules")
+            .trailing_slash()
+            .build())
     }
 }
 
 #[derive(Debug, Deserialize, Builder, Serialize)]
+#[builder(build_fn(error = "crate::Error"))]
 pub struct RuleRequest {
-    domain_id: String,
+    #[builder(
+        setter(into),
+        field(
+            type = "NameInput",
+            build = "self.domain_id.required(\"domain_id\")?"
+        )
+    )]
+    domain_id: DomainRef,
     rule_id: String,
 }
 
 impl AsUrl for RuleRequest {
-    fn as_url_path(self) -> String {
+    fn as_url_path(self) -> Result<String, Error> {
         let Self { domain_id, rule_id } = self;
-        format!(
-            "/api/v2/domains/{domain_id}/rules/{rule_id}"
-        )
+        Ok(rule_path(domain_id, rule_id).build())
     }
 }
 
+fn rule_path(
+    domain_id: DomainRef,
+    rule_id: String,
+) -> ApiPath {
+    ApiPath::v2()
+        .segment("domains")
+        .segment(domain_id)
+        .segment("rules")
+        .segment(rule_id)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListRulesResponse {
     pub rules: Vec<Rule>,
@@ -148,27 +215,27 @@ pub trait ApiRuleEndpoints {
         &self,
         request: CreateRuleRequest,
         data: Rule,
-    ) -> Result<Rule, Report>;
+    ) -> Result<Rule, Error>;
     async fn enable_rule(
         &self,
         request: EnableRuleRequest,
-    ) -> Result<ResponseStatus, Report>;
+    ) -> Result<ResponseStatus, Error>;
     async fn disable_rule(
         &self,
         request: DisableRuleRequest,
-    ) -> Result<ResponseStatus, Report>;
+    ) -> Result<ResponseStatus, Error>;
     async fn list_rules(
         &self,
         request: ListRulesRequest,
-    ) -> Result<ListRulesResponse, Report>;
+    ) -> Result<ListRulesResponse, Error>;
     async fn get_rule(
         &self,
         request: RuleRequest,
-    ) -> Result<Rule, Report>;
+    ) -> Result<Rule, Error>;
     async fn delete_rule(
         &self,
         request: RuleRequest,
-    ) -> Result<Rule, Report>;
+    ) -> Result<Rule, Error>;
 }
 
 #[async_trait]
@@ -177,37 +244,37 @@ impl ApiRuleEndpoints for Mailinator {
         &self,
         request: CreateRuleRequest,
         data: Rule,
-    ) -> Result<Rule, Report> {
-        self.post_json(request.as_url_path(), data).await
+    ) -> Result<Rule, Error> {
+        self.post_json(request.as_url_path()?, data).await
     }
     async fn enable_rule(
         &self,
         request: EnableRuleRequest,
-    ) -> Result<ResponseStatus, Report> {
-        self.put(request.as_url_path()).await
+    ) -> Result<ResponseStatus, Error> {
+        self.put(request.as_url_path()?).await
     }
     async fn disable_rule(
         &self,
         request: DisableRuleRequest,
-    ) -> Result<ResponseStatus, Report> {
-        self.put(request.as_url_path()).await
+    ) -> Result<ResponseStatus, Error> {
+        self.put(request.as_url_path()?).await
     }
     async fn list_rules(
         &self,
         request: ListRulesRequest,
-    ) -> Result<ListRulesResponse, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<ListRulesResponse, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn get_rule(
         &self,
         request: RuleRequest,
-    ) -> Result<Rule, Report> {
-        self.get(request.as_url_path()).await
+    ) -> Result<Rule, Error> {
+        self.get(request.as_url_path()?).await
     }
     async fn delete_rule(
         &self,
         request: RuleRequest,
-    ) -> Result<Rule, Report> {
-        self.delete(request.as_url_path()).await
+    ) -> Result<Rule, Error> {
+        self.delete(request.as_url_path()?).await
     }
 }
@@ -1,21 +1,21 @@
 use crate::client::Mailinator;
+use crate::Error;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use eyre::Report;
 use serde::Deserialize;
 
 #[async_trait]
 pub trait ApiStatEndpoints {
     async fn get_usage_statistica(
         &self,
-    ) -> Result<UsageStatistica, Report>;
+    ) -> Result<UsageStatistica, Error>;
 }
 
 #[async_trait]
 impl ApiStatEndpoints for Mailinator {
     async fn get_usage_statistica(
         &self,
-    ) -> Result<UsageStatistica, Report> {
+    ) -> Result<UsageStatistica, Error> {
         let url_path = "/api/v2/team/stats";
         self.get(url_path.to_owned()).await
     }
@@ -0,0 +1,323 @@
+//! Synchronous facade over the async [`crate::prelude::Mailinator`].
+//!
+//! Every method mirrors the one of the async endpoint traits and
+//! takes the same request and response types. Calls are driven by
+//! a private current thread runtime, so they must not be made from
+//! within an async context.
+//!
+//! ```rust,no_run
+//! use mailinator_rs::blocking::Mailinator;
+//! use mailinator_rs::prelude::FetchMessageRequestBuilder;
+//!
+//! let client = Mailinator::new(None, Some(String::from("XXXXX-XXXXXX")));
+//!
+//! let request = FetchMessageRequestBuilder::default()
+//!     .domain(String::from("example.com"))
+//!     .inbox(Some(String::from("testinbox")))
+//!     .message_id(String::from("msg_id"))
+//!     .build()
+//!     .expect("failed to build request");
+//!
+//! match client.fetch_message(request) {
+//!     Err(e) => println!("Error: {e}"),
+//!     Ok(msg) => println!("{msg:?}"),
+//! }
+//! ```
+
+// Errors are the ones of the mirrored async methods.
+#![allow(clippy::missing_errors_doc)]
+
+use crate::api::{
+    domains::{
+        ApiDomainEndpoints, Domain, DomainRequest,
+        DomainResponse,
+    },
+    message::{
+        attachment::{
+            AttachmentFile, AttachmentInfo,
+            FetchAttachmentRequest,
+            FetchListOfAttachmentRequest,
+            FetchListOfAttachmentResponse,
+        },
+        inbox::{FetchInboxRequest, FetchInboxResponse},
+        latest::{
+            FetchLatestMessagesRequest,
+            FetchLatestMessagesResponse,
+        },
+        link::{FetchLinkRequest, FetchLinkResponse},
+        sms::{
+            FetchSmsInboxRequest, FetchSmsMessageRequest,
+            SmsInbox, SmsMessage,
+        },
+        smtp::SmtpLogResponse,
+        ApiMessageEndpoints, DeleteAllDomainMessageRequest,
+        DeleteAllInboxMessageRequest, DeleteMessageRequest,
+        DeleteMessageResponse, Email, FetchMessageRequest,
+        InjectMessageRequest, InjectMessageResponse,
+        NewEmail, RawMessage,
+    },
+    rules::{
+        ApiRuleEndpoints, CreateRuleRequest,
+        DisableRuleRequest, EnableRuleRequest,
+        ListRulesRequest, ListRulesResponse, Rule,
+        RuleRequest,
+    },
+    stats::{ApiStatEndpoints, UsageStatistica},
+    ResponseStatus,
+};
+use crate::{client, Error};
+use std::{future::Future, io::Write, sync::Arc};
+use tokio::runtime::{Builder, Runtime};
+
+/// Blocking Mailinator client, cheap to clone.
+#[derive(Debug, Clone)]
+pub struct Mailinator {
+    inner: client::Mailinator,
+    runtime: Arc<Runtime>,
+}
+
+impl Mailinator {
+    /// Creates a client from an optional api url and token, both
+    /// falling back to the environment.
+    ///
+    /// # Panics
+    /// Panics when the client can not be built, see
+    /// [`Mailinator::from_async`] for a fallible alternative.
+    #[must_use]
+    pub fn new(
+        apiurl: Option<String>,
+        apitoken: Option<String>,
+    ) -> Self {
+        Self::from_async(client::Mailinator::new(
+            apiurl, apitoken,
+        ))
+        .expect(
+            "Failed to build blocking mailinator client",
+        )
+    }
+
+    /// Wraps an async client, usually made with
+    /// [`client::Mailinator::builder`].
+    ///
+    /// # Errors
+    /// Returns [`Error::Builder`] when the runtime can not be
+    /// started.
+    pub fn from_async(
+        inner: client::Mailinator,
+    ) -> Result<Self, Error> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Builder(format!(
+                    "failed to start blocking runtime: {e}"
+                ))
+            })?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+/// Message Api, see [`ApiMessageEndpoints`].
+impl Mailinator {
+    pub fn fetch_inbox(
+        &self,
+        request: FetchInboxRequest,
+    ) -> Result<FetchInboxResponse, Error> {
+        self.block_on(self.inner.fetch_inbox(request))
+    }
+    pub fn fetch_message(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<Email, Error> {
+        self.block_on(self.inner.fetch_message(request))
+    }
+    pub fn fetch_latest_messages(
+        &self,
+        request: FetchLatestMessagesRequest,
+    ) -> Result<FetchLatestMessagesResponse, Error> {
+        self.block_on(
+            self.inner.fetch_latest_messages(request),
+        )
+    }
+    pub fn fetch_raw_message(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<RawMessage, Error> {
+        self.block_on(self.inner.fetch_raw_message(request))
+    }
+    pub fn fetch_smtp_log(
+        &self,
+        request: FetchMessageRequest,
+    ) -> Result<SmtpLogResponse, Error> {
+        self.block_on(self.inner.fetch_smtp_log(request))
+    }
+    pub fn fetch_sms_inbox(
+        &self,
+        request: FetchSmsInboxRequest,
+    ) -> Result<SmsInbox, Error> {
+        self.block_on(self.inner.fetch_sms_inbox(request))
+    }
+    pub fn fetch_sms_message(
+        &self,
+        request: FetchSmsMessageRequest,
+    ) -> Result<SmsMessage, Error> {
+        self.block_on(self.inner.fetch_sms_message(request))
+    }
+    pub fn fetch_list_of_attachments(
+        &self,
+        request: FetchListOfAttachmentRequest,
+    ) -> Result<FetchListOfAttachmentResponse, Error> {
+        self.block_on(
+            self.inner.fetch_list_of_attachments(request),
+        )
+    }
+    pub fn fetch_links(
+        &self,
+        request: FetchLinkRequest,
+    ) -> Result<FetchLinkResponse, Error> {
+        self.block_on(self.inner.fetch_links(request))
+    }
+    pub fn fetch_attachment(
+        &self,
+        request: FetchAttachmentRequest,
+    ) -> Result<AttachmentFile, Error> {
+        self.block_on(self.inner.fetch_attachment(request))
+    }
+    /// Streams the attachment into `writer` chunk by chunk.
+    pub fn download_attachment(
+        &self,
+        request: FetchAttachmentRequest,
+        writer: &mut dyn Write,
+    ) -> Result<AttachmentInfo, Error> {
+        let (mut info, mut resp) = self.block_on(
+            self.inner.open_attachment(request),
+        )?;
+        while let Some(chunk) =
+            self.block_on(resp.chunk())?
+        {
+            writer.write_all(&chunk)?;
+            info.size += chunk.len() as u64;
+        }
+        writer.flush()?;
+        Ok(info)
+    }
+    pub fn delete_all_domain_messages(
+        &self,
+        request: DeleteAllDomainMessageRequest,
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.block_on(
+            self.inner.delete_all_domain_messages(request),
+        )
+    }
+    pub fn delete_all_inbox_messages(
+        &self,
+        request: DeleteAllInboxMessageRequest,
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.block_on(
+            self.inner.delete_all_inbox_messages(request),
+        )
+    }
+    pub fn delete_message(
+        &self,
+        request: DeleteMessageRequest,
+    ) -> Result<DeleteMessageResponse, Error> {
+        self.block_on(self.inner.delete_message(request))
+    }
+    pub fn inject_message(
+        &self,
+        request: InjectMessageRequest,
+        email: NewEmail,
+    ) -> Result<InjectMessageResponse, Error> {
+        self.block_on(
+            self.inner.inject_message(request, email),
+        )
+    }
+}
+
+/// Rule Api, see [`ApiRuleEndpoints`].
+impl Mailinator {
+    pub fn create_rule(
+        &self,
+        request: CreateRuleRequest,
+        data: Rule,
+    ) -> Result<Rule, Error> {
+        self.block_on(self.inner.create_rule(request, data))
+    }
+    pub fn enable_rule(
+        &self,
+        request: EnableRuleRequest,
+    ) -> Result<ResponseStatus, Error> {
+        self.block_on(self.inner.enable_rule(request))
+    }
+    pub fn disable_rule(
+        &self,
+        request: DisableRuleRequest,
+    ) -> Result<ResponseStatus, Error> {
+        self.block_on(self.inner.disable_rule(request))
+    }
+    pub fn list_rules(
+        &self,
+        request: ListRulesRequest,
+    ) -> Result<ListRulesResponse, Error> {
+        self.block_on(self.inner.list_rules(request))
+    }
+    pub fn get_rule(
+        &self,
+        request: RuleRequest,
+    ) -> Result<Rule, Error> {
+        self.block_on(self.inner.get_rule(request))
+    }
+    pub fn delete_rule(
+        &self,
+        request: RuleRequest,
+    ) -> Result<Rule, Error> {
+        self.block_on(self.inner.delete_rule(request))
+    }
+}
+
+/// Domain Api, see [`ApiDomainEndpoints`].
+impl Mailinator {
+    pub fn get_all_domains(
+        &self,
+    ) -> Result<DomainResponse, Error> {
+        self.block_on(self.inner.get_all_domains())
+    }
+    pub fn get_domain(
+        &self,
+        request: DomainRequest,
+    ) -> Result<Domain, Error> {
+        self.block_on(self.inner.get_domain(request))
+    }
+    pub fn create_private_domain(
+        &self,
+        request: DomainRequest,
+    ) -> Result<ResponseStatus, Error> {
+        self.block_on(
+            self.inner.create_private_domain(request),
+        )
+    }
+    pub fn delete_private_domain(
+        &self,
+        request: DomainRequest,
+    ) -> Result<ResponseStatus, Error> {
+        self.block_on(
+            self.inner.delete_private_domain(request),
+        )
+    }
+}
+
+/// Stat Api, see [`ApiStatEndpoints`].
+impl Mailinator {
+    pub fn get_usage_statistica(
+        &self,
+    ) -> Result<UsageStatistica, Error> {
+        self.block_on(self.inner.get_usage_statistica())
+    }
+}
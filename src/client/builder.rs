@@ -0,0 +1,288 @@
+use super::{
+    limiter::RateLimiter, Mailinator, RateLimit,
+    RetryPolicy,
+};
+use crate::config::EnvCfg;
+use crate::Error;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, AUTHORIZATION,
+        USER_AGENT,
+    },
+    redirect::Policy,
+    Certificate, Client, ClientBuilder, Proxy, Url,
+};
+use std::{sync::Arc, time::Duration};
+
+/// Fallible builder for [`Mailinator`].
+///
+/// The api url and token fall back to the `MAILINATOR_API_URL`
+/// and `MAILINATOR_API_TOKEN` environment variables.
+///
+/// ```rust,no_run
+/// use mailinator_rs::prelude::Mailinator;
+/// use std::time::Duration;
+///
+/// let client = Mailinator::builder()
+///     .api_token("XXXXX-XXXXXX")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("qa-suite/1.0")
+///     .build()
+///     .expect("failed to build client");
+/// ```
+#[derive(Debug, Default)]
+#[must_use]
+pub struct MailinatorBuilder {
+    api_url: Option<String>,
+    api_token: Option<String>,
+    user_agent: Option<String>,
+    headers: HeaderMap,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxies: Vec<ProxyConfig>,
+    root_certificates: Vec<Certificate>,
+    client: Option<Client>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+}
+
+#[derive(Debug)]
+enum ProxyConfig {
+    Http(String),
+    Https(String),
+    Custom(Proxy),
+}
+
+impl MailinatorBuilder {
+    pub fn api_url(
+        mut self,
+        url: impl Into<String>,
+    ) -> Self {
+        self.api_url = Some(url.into());
+        self
+    }
+
+    pub fn api_token(
+        mut self,
+        token: impl Into<String>,
+    ) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    pub fn user_agent(
+        mut self,
+        agent: impl Into<String>,
+    ) -> Self {
+        self.user_agent = Some(agent.into());
+        self
+    }
+
+    /// Adds a header sent along with every request.
+    pub fn default_header(
+        mut self,
+        name: HeaderName,
+        value: HeaderValue,
+    ) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    pub const fn connect_timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout of a whole request, from connection to the end of
+    /// the response body.
+    pub const fn timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Proxies plain http traffic through `url`.
+    pub fn http_proxy(
+        mut self,
+        url: impl Into<String>,
+    ) -> Self {
+        self.proxies.push(ProxyConfig::Http(url.into()));
+        self
+    }
+
+    /// Proxies https traffic through `url`.
+    pub fn https_proxy(
+        mut self,
+        url: impl Into<String>,
+    ) -> Self {
+        self.proxies.push(ProxyConfig::Https(url.into()));
+        self
+    }
+
+    /// Adds an already configured [`Proxy`].
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(ProxyConfig::Custom(proxy));
+        self
+    }
+
+    /// Trusts an additional root certificate.
+    pub fn add_root_certificate(
+        mut self,
+        cert: Certificate,
+    ) -> Self {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Uses a pre-built [`Client`].
+    ///
+    /// Transport options (timeouts, proxies and certificates) can
+    /// not be combined with it and must be set on the client itself.
+    /// Link checks also go through it, following redirects as its
+    /// own policy says.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn retry_policy(
+        mut self,
+        policy: RetryPolicy,
+    ) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub const fn rate_limit(
+        mut self,
+        limit: RateLimit,
+    ) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Builds the [`Mailinator`] client.
+    ///
+    /// # Errors
+    /// Returns [`Error::Builder`] when no token is available, when
+    /// the api url, user agent or a proxy url is invalid, or when
+    /// transport options are combined with a custom client.
+    pub fn build(self) -> Result<Mailinator, Error> {
+        let EnvCfg { api_url, api_token } = EnvCfg::new();
+
+        let api_url = self.api_url.unwrap_or(api_url);
+        let url = Url::parse(&api_url).map_err(|e| {
+            Error::Builder(format!(
+                "invalid api url {api_url}: {e}"
+            ))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Error::Builder(format!(
+                "invalid api url {api_url}: unsupported scheme"
+            )));
+        }
+
+        let token = self
+            .api_token
+            .or(api_token)
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| {
+                Error::Builder(String::from(
+                    "no api token provided nor found in MAILINATOR_API_TOKEN",
+                ))
+            })?;
+
+        let mut headers = self.headers;
+        let mut auth = HeaderValue::try_from(token)
+            .map_err(|_| {
+                Error::Builder(String::from(
+                    "api token is not a valid header value",
+                ))
+            })?;
+        auth.set_sensitive(true);
+        headers.insert(AUTHORIZATION, auth);
+        if let Some(agent) = self.user_agent {
+            let agent = HeaderValue::try_from(agent).map_err(|_| {
+                Error::Builder(String::from(
+                    "user agent is not a valid header value",
+                ))
+            })?;
+            headers.insert(USER_AGENT, agent);
+        }
+
+        let transport_options =
+            self.connect_timeout.is_some()
+                || self.timeout.is_some()
+                || !self.proxies.is_empty()
+                || !self.root_certificates.is_empty();
+
+        // Links are checked with a client of the same transport
+        // that leaves redirects to the caller, unless a custom
+        // client is given.
+        let (client, link_client) = match self.client {
+            Some(_) if transport_options => {
+                return Err(Error::Builder(String::from(
+                    "transport options can not be combined with a custom client",
+                )))
+            }
+            Some(client) => (client.clone(), client),
+            None => {
+                let transport = || -> Result<ClientBuilder, Error> {
+                    let mut builder = Client::builder();
+                    if let Some(timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(timeout);
+                    }
+                    if let Some(timeout) = self.timeout {
+                        builder = builder.timeout(timeout);
+                    }
+                    for proxy in &self.proxies {
+                        builder = builder.proxy(proxy.to_proxy()?);
+                    }
+                    for cert in &self.root_certificates {
+                        builder =
+                            builder.add_root_certificate(cert.clone());
+                    }
+                    Ok(builder)
+                };
+                (
+                    transport()?.build()?,
+                    transport()?.redirect(Policy::none()).build()?,
+                )
+            }
+        };
+
+        Ok(Mailinator {
+            client,
+            link_client,
+            headers,
+            api_url: api_url
+                .trim_end_matches('/')
+                .to_owned(),
+            retry_policy: self.retry_policy,
+            limiter: self.rate_limit.map(|limit| {
+                Arc::new(RateLimiter::new(limit))
+            }),
+        })
+    }
+}
+
+impl ProxyConfig {
+    fn to_proxy(&self) -> Result<Proxy, Error> {
+        let (proxy, url) = match self {
+            Self::Http(url) => (Proxy::http(url), url),
+            Self::Https(url) => (Proxy::https(url), url),
+            Self::Custom(proxy) => return Ok(proxy.clone()),
+        };
+        proxy.map_err(|e| {
+            Error::Builder(format!(
+                "invalid proxy url {url}: {e}"
+            ))
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Request quota enforced on the client side.
+///
+/// ```rust
+/// use mailinator_rs::prelude::RateLimit;
+/// use std::num::NonZeroU32;
+///
+/// // 100 requests per minute, at most 10 in a row.
+/// let limit = RateLimit::per_minute(NonZeroU32::new(100).unwrap())
+///     .with_burst(NonZeroU32::new(10).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    requests: NonZeroU32,
+    period: Duration,
+    burst: NonZeroU32,
+}
+
+impl RateLimit {
+    #[must_use]
+    pub const fn per_second(requests: NonZeroU32) -> Self {
+        Self {
+            requests,
+            period: Duration::from_secs(1),
+            burst: requests,
+        }
+    }
+
+    #[must_use]
+    pub const fn per_minute(requests: NonZeroU32) -> Self {
+        Self {
+            requests,
+            period: Duration::from_secs(60),
+            burst: requests,
+        }
+    }
+
+    /// Maximum number of requests allowed back to back, defaults
+    /// to the number of requests of the period.
+    #[must_use]
+    pub const fn with_burst(
+        mut self,
+        burst: NonZeroU32,
+    ) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Token bucket shared by every clone of a [`super::Mailinator`].
+#[derive(Debug)]
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+    throttled_nanos: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_sec: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        let capacity = f64::from(limit.burst.get());
+        Self {
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                capacity,
+                refill_per_sec: f64::from(
+                    limit.requests.get(),
+                ) / limit
+                    .period
+                    .as_secs_f64(),
+                last: Instant::now(),
+            }),
+            throttled_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Takes one token, waiting for it to be refilled if the bucket
+    /// is empty.
+    ///
+    /// The token is reserved before sleeping so concurrent callers
+    /// queue up instead of racing for the same refill.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket =
+                self.bucket.lock().unwrap_or_else(
+                    std::sync::PoisonError::into_inner,
+                );
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last);
+            bucket.last = now;
+            bucket.tokens = elapsed
+                .as_secs_f64()
+                .mul_add(
+                    bucket.refill_per_sec,
+                    bucket.tokens,
+                )
+                .min(bucket.capacity);
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(
+                -bucket.tokens / bucket.refill_per_sec,
+            )
+        };
+        tracing::debug!(
+            ?wait,
+            "throttling mailinator request"
+        );
+        self.throttled_nanos.fetch_add(
+            u64::try_from(wait.as_nanos())
+                .unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        tokio::time::sleep(wait).await;
+    }
+
+    pub fn throttled(&self) -> Duration {
+        Duration::from_nanos(
+            self.throttled_nanos.load(Ordering::Relaxed),
+        )
+    }
+}
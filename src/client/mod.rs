@@ -1,73 +1,142 @@
-use crate::config::EnvCfg;
-use eyre::Report;
+use crate::{error::ApiError, Error};
 use futures::TryFutureExt;
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Body, Client, RequestBuilder, Response,
+    header::{HeaderMap, RETRY_AFTER, USER_AGENT},
+    Body, Client, Method, Request, RequestBuilder,
+    Response, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
+use std::{sync::Arc, time::Duration};
+
+mod builder;
+mod limiter;
+mod retry;
+
+pub use builder::MailinatorBuilder;
+pub use limiter::RateLimit;
+use limiter::RateLimiter;
+pub use retry::{RetryPolicy, RetryPolicyBuilder};
 
 #[derive(Debug, Clone)]
 pub struct Mailinator {
     client: Client,
+    /// Client for urls outside of the api, not following redirects
+    /// unless it is a custom client.
+    link_client: Client,
     headers: HeaderMap,
     api_url: String,
+    retry_policy: RetryPolicy,
+    limiter: Option<Arc<RateLimiter>>,
 }
 
 impl Mailinator {
+    /// Creates a client from an optional api url and token, both
+    /// falling back to the environment.
+    ///
+    /// # Panics
+    /// Panics when the client can not be built, see
+    /// [`Mailinator::builder`] for a fallible alternative.
     #[must_use]
     pub fn new(
         apiurl: Option<String>,
         apitoken: Option<String>,
     ) -> Self {
-        let mut headers = HeaderMap::new();
-        let EnvCfg { api_url, api_token } = EnvCfg::new();
-
-        let new_api_url = apiurl.unwrap_or(api_url);
-
-        headers.insert(
-            "Authorization",
-            HeaderValue::try_from(
-                apitoken.or(api_token).expect(
-                    "No valid api token were provided",
-                ),
-            )
-            .expect("Cannot build authorization"),
-        );
-
-        let client = Client::builder()
+        let mut builder = Self::builder();
+        if let Some(url) = apiurl {
+            builder = builder.api_url(url);
+        }
+        if let Some(token) = apitoken {
+            builder = builder.api_token(token);
+        }
+        builder
             .build()
-            .expect("Failed to build http client");
+            .expect("Failed to build mailinator client")
+    }
 
-        Self {
-            client,
-            headers,
-            api_url: new_api_url,
-        }
+    pub fn builder() -> MailinatorBuilder {
+        MailinatorBuilder::default()
+    }
+
+    /// Replaces the [`RetryPolicy`] applied to every request.
+    #[must_use]
+    pub fn with_retry_policy(
+        mut self,
+        policy: RetryPolicy,
+    ) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Throttles requests to the given [`RateLimit`].
+    ///
+    /// The quota is shared by this client and every clone made
+    /// afterwards.
+    #[must_use]
+    pub fn with_rate_limit(
+        mut self,
+        limit: RateLimit,
+    ) -> Self {
+        self.limiter =
+            Some(Arc::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Total time requests spent waiting on the rate limiter,
+    /// across every clone sharing it.
+    #[must_use]
+    pub fn throttled_time(&self) -> Duration {
+        self.limiter
+            .as_ref()
+            .map_or(Duration::ZERO, |l| l.throttled())
     }
 
     pub(crate) async fn get<T>(
         &self,
         path: String,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned + Sync + Send,
     {
         HttpRequest::get(self, path)
-            .and_then(HttpRequest::send)
+            .and_then(|req| HttpRequest::send(self, req))
             .and_then(HttpRequest::parse_json)
             .await
     }
 
+    /// Sends a `GET` and hands back the successful response as is,
+    /// for bodies that are not json.
+    pub(crate) async fn get_response(
+        &self,
+        path: String,
+    ) -> Result<Response, Error> {
+        HttpRequest::get(self, path)
+            .and_then(|req| HttpRequest::send(self, req))
+            .await
+    }
+
+    /// Requests a url outside of the api through the link client,
+    /// with the configured user agent but without the api token.
+    pub(crate) async fn request_external(
+        &self,
+        method: Method,
+        url: &str,
+    ) -> Result<Response, Error> {
+        let mut req = self.link_client.request(method, url);
+        if let Some(agent) = self.headers.get(USER_AGENT) {
+            req = req.header(USER_AGENT, agent);
+        }
+        Ok(req.send().await?)
+    }
+
     pub(crate) async fn put<T>(
         &self,
         path: String,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned + Sync + Send,
     {
         HttpRequest::put(self, path)
-            .and_then(HttpRequest::send)
+            .and_then(|req| HttpRequest::send(self, req))
             .and_then(HttpRequest::parse_json)
             .await
     }
@@ -76,13 +145,13 @@ impl Mailinator {
         &self,
         path: String,
         data: Data,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         Data: Serialize + Sync + Send + Into<Body>,
         T: DeserializeOwned + Sync + Send,
     {
         HttpRequest::post(self, path, data)
-            .and_then(HttpRequest::send)
+            .and_then(|req| HttpRequest::send(self, req))
             .and_then(HttpRequest::parse_json)
             .await
     }
@@ -91,13 +160,13 @@ impl Mailinator {
         &self,
         path: String,
         jdata: Jdata,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         Jdata: Serialize + Sync + Send,
         T: DeserializeOwned + Sync + Send,
     {
         HttpRequest::post_json(self, path, jdata)
-            .and_then(HttpRequest::send)
+            .and_then(|req| HttpRequest::send(self, req))
             .and_then(HttpRequest::parse_json)
             .await
     }
@@ -105,12 +174,12 @@ impl Mailinator {
     pub(crate) async fn delete<T>(
         &self,
         path: String,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned + Sync + Send,
     {
         HttpRequest::delete(self, path)
-            .and_then(HttpRequest::send)
+            .and_then(|req| HttpRequest::send(self, req))
             .and_then(HttpRequest::parse_json)
             .await
     }
@@ -123,11 +192,12 @@ impl HttpRequest {
     async fn get(
         inner: &Mailinator,
         path: String,
-    ) -> Result<RequestBuilder, Report> {
+    ) -> Result<RequestBuilder, Error> {
         let Mailinator {
             client,
             headers,
             api_url,
+            ..
         } = inner;
         Ok(client
             .get(format!("{api_url}{path}"))
@@ -137,11 +207,12 @@ impl HttpRequest {
     async fn put(
         inner: &Mailinator,
         path: String,
-    ) -> Result<RequestBuilder, Report> {
+    ) -> Result<RequestBuilder, Error> {
         let Mailinator {
             client,
             headers,
             api_url,
+            ..
         } = inner;
         Ok(client
             .put(format!("{api_url}{path}"))
@@ -152,7 +223,7 @@ impl HttpRequest {
         inner: &Mailinator,
         path: String,
         data: Data,
-    ) -> Result<RequestBuilder, Report>
+    ) -> Result<RequestBuilder, Error>
     where
         Data: Serialize + Sync + Send + Into<Body>,
     {
@@ -160,6 +231,7 @@ impl HttpRequest {
             client,
             headers,
             api_url,
+            ..
         } = inner;
         Ok(client
             .post(format!("{api_url}{path}"))
@@ -171,7 +243,7 @@ impl HttpRequest {
         inner: &Mailinator,
         path: String,
         jdata: Jdata,
-    ) -> Result<RequestBuilder, Report>
+    ) -> Result<RequestBuilder, Error>
     where
         Jdata: Serialize + Sync + Send,
     {
@@ -179,6 +251,7 @@ impl HttpRequest {
             client,
             headers,
             api_url,
+            ..
         } = inner;
         Ok(client
             .post(format!("{api_url}{path}"))
@@ -189,11 +262,12 @@ impl HttpRequest {
     async fn delete(
         inner: &Mailinator,
         path: String,
-    ) -> Result<RequestBuilder, Report> {
+    ) -> Result<RequestBuilder, Error> {
         let Mailinator {
             client,
             headers,
             api_url,
+            ..
         } = inner;
         Ok(client
             .delete(format!("{api_url}{path}"))
@@ -201,23 +275,108 @@ impl HttpRequest {
     }
 
     async fn send(
+        inner: &Mailinator,
         req: RequestBuilder,
-    ) -> Result<Response, Report> {
-        match req.send().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(e.into()),
+    ) -> Result<Response, Error> {
+        let policy = &inner.retry_policy;
+        let (client, request) = req.build_split();
+        let mut request = request?;
+        let retryable = policy.allows(request.method());
+        let mut attempt = 1;
+        loop {
+            let next = (retryable
+                && attempt < policy.max_attempts)
+                .then(|| request.try_clone())
+                .flatten();
+            if let Some(limiter) = &inner.limiter {
+                limiter.acquire().await;
+            }
+            match (
+                Self::execute(&client, request).await,
+                next,
+            ) {
+                (Err(e), Some(next))
+                    if policy.should_retry(&e) =>
+                {
+                    let delay = policy.delay(attempt, &e);
+                    tracing::warn!(
+                        error = %e,
+                        attempt,
+                        ?delay,
+                        "retrying mailinator request"
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    attempt += 1;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+
+    async fn execute(
+        client: &Client,
+        request: Request,
+    ) -> Result<Response, Error> {
+        let method = request.method().clone();
+        let url = request.url();
+        let path = url.query().map_or_else(
+            || url.path().to_owned(),
+            |q| format!("{}?{q}", url.path()),
+        );
+
+        let resp = client.execute(request).await?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
         }
+        let retry_after = retry_after(resp.headers());
+        let body = resp.text().await.unwrap_or_default();
+        let error = Box::new(ApiError::new(
+            method, path, status, body,
+        ));
+        tracing::debug!(%error, "mailinator request failed");
+        Err(match status {
+            StatusCode::UNAUTHORIZED
+            | StatusCode::FORBIDDEN => {
+                Error::Unauthorized(error)
+            }
+            StatusCode::NOT_FOUND => Error::NotFound(error),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Error::RateLimited { retry_after, error }
+            }
+            _ => Error::Http(error),
+        })
     }
 
     async fn parse_json<T>(
         resp: Response,
-    ) -> Result<T, Report>
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned + Sync + Send,
     {
-        match resp.json::<T>().await {
-            Ok(j) => Ok(j),
-            Err(e) => Err(e.into()),
-        }
+        let payload = resp.bytes().await?;
+        serde_json::from_slice(&payload).map_err(|source| {
+            Error::Decode {
+                source,
+                payload: String::from_utf8_lossy(&payload)
+                    .into_owned(),
+            }
+        })
+    }
+}
+
+/// Reads a `Retry-After` header given either as delay seconds or
+/// as an http date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value =
+        headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
     }
+    let date =
+        chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
 }
@@ -0,0 +1,124 @@
+use crate::Error;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Controls how failed requests are retried.
+///
+/// By default only idempotent `GET` and `DELETE` requests are
+/// retried, on `429` and transient `5xx` answers as well as on
+/// connection errors and timeouts. Add `POST` to `retry_methods` to
+/// opt `inject_message` or `create_rule` in.
+///
+/// ```rust
+/// use mailinator_rs::prelude::RetryPolicyBuilder;
+/// use reqwest::Method;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicyBuilder::default()
+///     .max_attempts(5)
+///     .base_delay(Duration::from_millis(500))
+///     .retry_methods(vec![Method::GET, Method::DELETE, Method::POST])
+///     .build()
+///     .expect("failed to build retry policy");
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(error = "crate::Error"))]
+pub struct RetryPolicy {
+    /// Total number of attempts, the first one included.
+    #[builder(default = "3")]
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every attempt.
+    #[builder(default = "Duration::from_millis(250)")]
+    pub base_delay: Duration,
+    /// Upper bound for any delay, `Retry-After` included.
+    #[builder(default = "Duration::from_secs(10)")]
+    pub max_delay: Duration,
+    /// Randomize each delay between half and all of its value.
+    #[builder(default = "true")]
+    pub jitter: bool,
+    /// Wait as long as the `Retry-After` header asks when present.
+    #[builder(default = "true")]
+    pub respect_retry_after: bool,
+    #[builder(default = "default_retry_statuses()")]
+    pub retry_statuses: Vec<StatusCode>,
+    #[builder(
+        default = "vec![Method::GET, Method::DELETE]"
+    )]
+    pub retry_methods: Vec<Method>,
+}
+
+fn default_retry_statuses() -> Vec<StatusCode> {
+    vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ]
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicyBuilder::default()
+            .build()
+            .expect("every field has a default")
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn allows(&self, method: &Method) -> bool {
+        self.max_attempts > 1
+            && self.retry_methods.contains(method)
+    }
+
+    pub(crate) fn should_retry(
+        &self,
+        error: &Error,
+    ) -> bool {
+        match error {
+            Error::Transport(e) => {
+                e.is_connect() || e.is_timeout()
+            }
+            e => e.status().is_some_and(|s| {
+                self.retry_statuses.contains(&s)
+            }),
+        }
+    }
+
+    /// Delay to wait before the attempt following `attempt`.
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        error: &Error,
+    ) -> Duration {
+        if self.respect_retry_after {
+            if let Error::RateLimited {
+                retry_after: Some(after),
+                ..
+            } = error
+            {
+                return (*after).min(self.max_delay);
+            }
+        }
+        let exp =
+            2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .base_delay
+            .saturating_mul(exp)
+            .min(self.max_delay);
+        if self.jitter {
+            delay.mul_f64(fastrand::f64().mul_add(0.5, 0.5))
+        } else {
+            delay
+        }
+    }
+}
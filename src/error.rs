@@ -0,0 +1,176 @@
+use crate::api::message::inbox::Msg;
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use std::{fmt, time::Duration};
+use thiserror::Error;
+
+/// Every failure surfaced by the Mailinator endpoints.
+///
+/// Status based variants carry an [`ApiError`] describing the
+/// request that failed and what Mailinator answered.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The request could not be sent or the response could not
+    /// be read (connection refused, dns, tls, timeouts...).
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// A downloaded file could not be written.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Mailinator answered with a non success status that has no
+    /// dedicated variant.
+    #[error("{0}")]
+    Http(Box<ApiError>),
+    /// Mailinator answered `429 Too Many Requests`.
+    #[error("rate limited (retry after {retry_after:?}): {error}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        error: Box<ApiError>,
+    },
+    /// The api token was rejected (`401` or `403`).
+    #[error("authentication failed: {0}")]
+    Unauthorized(Box<ApiError>),
+    /// The domain, inbox, message or rule does not exist.
+    #[error("not found: {0}")]
+    NotFound(Box<ApiError>),
+    /// The response body did not match the expected shape.
+    #[error("failed to decode response body: {source}")]
+    Decode {
+        source: serde_json::Error,
+        payload: String,
+    },
+    /// No message matched before the deadline of a wait, `seen`
+    /// holds every message listed while polling, once per id.
+    #[error("no matching message after {elapsed:?}, {} seen", seen.len())]
+    Timeout { elapsed: Duration, seen: Vec<Msg> },
+    /// No link of a message matched a [`LinkPattern`].
+    ///
+    /// [`LinkPattern`]: crate::prelude::LinkPattern
+    #[error("no link matches {pattern} among {} links", links.len())]
+    NoMatchingLink { pattern: String, links: Vec<String> },
+    /// Several distinct links of a message matched a
+    /// [`LinkPattern`].
+    ///
+    /// [`LinkPattern`]: crate::prelude::LinkPattern
+    #[error("{} links match {pattern}: {}", matches.len(), matches.join(", "))]
+    AmbiguousLink {
+        pattern: String,
+        matches: Vec<String>,
+    },
+    /// A request could not be built because a field is missing or
+    /// holds an invalid value.
+    #[error("invalid request: {0}")]
+    Builder(String),
+}
+
+impl Error {
+    /// The http status returned by Mailinator, if any.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Transport(e) => e.status(),
+            Self::Http(e)
+            | Self::Unauthorized(e)
+            | Self::NotFound(e)
+            | Self::RateLimited { error: e, .. } => {
+                Some(e.status)
+            }
+            Self::Io(_)
+            | Self::Decode { .. }
+            | Self::Timeout { .. }
+            | Self::NoMatchingLink { .. }
+            | Self::AmbiguousLink { .. }
+            | Self::Builder(_) => None,
+        }
+    }
+
+    /// The api error details, if Mailinator answered with a non
+    /// success status.
+    #[must_use]
+    pub const fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Http(e)
+            | Self::Unauthorized(e)
+            | Self::NotFound(e)
+            | Self::RateLimited { error: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<derive_builder::UninitializedFieldError>
+    for Error
+{
+    fn from(
+        e: derive_builder::UninitializedFieldError,
+    ) -> Self {
+        Self::Builder(e.to_string())
+    }
+}
+
+/// Error payload returned by Mailinator alongside a non success
+/// status, i.e. `{"status":"...","message":"..."}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiErrorBody {
+    pub status: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Describes a request that Mailinator answered with a non success
+/// status.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub method: Method,
+    pub path: String,
+    pub status: StatusCode,
+    /// The decoded error payload, when the body had the expected
+    /// shape.
+    pub details: Option<ApiErrorBody>,
+    /// The raw response body.
+    pub body: String,
+}
+
+impl ApiError {
+    pub(crate) fn new(
+        method: Method,
+        path: String,
+        status: StatusCode,
+        body: String,
+    ) -> Self {
+        let details = serde_json::from_str(&body).ok();
+        Self {
+            method,
+            path,
+            status,
+            details,
+            body,
+        }
+    }
+
+    /// The message reported by Mailinator, falling back to the raw
+    /// body.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        self.details
+            .as_ref()
+            .and_then(|d| d.message.as_deref())
+            .unwrap_or(&self.body)
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} {} returned {}: {}",
+            self.method,
+            self.path,
+            self.status,
+            self.message()
+        )
+    }
+}
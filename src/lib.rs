@@ -8,10 +8,16 @@
 extern crate derive_builder;
 
 mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 mod config;
+mod error;
+mod names;
 mod path;
 
+pub use error::{ApiError, ApiErrorBody, Error};
+
 pub mod prelude {
     pub use super::{
         api::{
@@ -20,21 +26,65 @@ pub mod prelude {
                 DomainRequestBuilder, DomainResponse,
             },
             message::{
+                address::Mailbox,
                 attachment::{
-                    Attachment,
+                    Attachment, AttachmentFile,
+                    AttachmentInfo, AttachmentKey,
+                    DownloadedAttachment,
+                    DownloadedAttachments,
+                    FetchAllAttachmentsRequestBuilder,
                     FetchAttachmentRequestBuilder,
                     FetchListOfAttachmentRequestBuilder,
                     FetchListOfAttachmentResponse,
                     LookupField,
                 },
+                headers::Headers,
                 inbox::{
                     FetchInboxRequestBuilder,
                     FetchInboxRequestQueryParamsBuilder,
-                    FetchInboxResponse, Msg, Sorting,
+                    FetchInboxResponse, InboxSelector, Msg,
+                    Sorting,
+                },
+                latest::{
+                    FetchLatestMessagesRequestBuilder,
+                    FetchLatestMessagesResponse, FetchMode,
+                    LatestCursor, LatestMessages,
+                    LatestMessagesRequestBuilder,
                 },
                 link::{
+                    CheckLinksRequestBuilder,
                     FetchLinkRequestBuilder,
-                    FetchLinkResponse,
+                    FetchLinkResponse, LinkKind,
+                    LinkPattern, LinkReport, LinkStatus,
+                    MatchedLink,
+                },
+                mime::{
+                    ContentType, EmbeddedFile, MimeNode,
+                    TextBody,
+                },
+                otp::{
+                    CodeCandidate, CodeExtractor,
+                    CodeSource,
+                },
+                paginate::{
+                    InboxPaginator,
+                    PaginateInboxRequestBuilder,
+                },
+                sms::{
+                    FetchSmsInboxRequestBuilder,
+                    FetchSmsMessageRequestBuilder,
+                    SmsInbox, SmsMessage, SmsNumber,
+                },
+                smtp::{
+                    SmtpEventKind, SmtpLogEvent,
+                    SmtpLogResponse,
+                },
+                wait::{
+                    AwaitMessageRequestBuilder,
+                    MessageFilter,
+                },
+                watch::{
+                    InboxWatch, WatchInboxRequestBuilder,
                 },
                 ApiMessageEndpoints,
                 DeleteAllDomainMessageRequestBuilder,
@@ -42,13 +92,15 @@ pub mod prelude {
                 DeleteMessageRequestBuilder,
                 DeleteMessageResponse, Email,
                 FetchMessageRequestBuilder,
-                FetchSmsMessageRequestBuilder,
                 InjectMessageRequestBuilder,
                 InjectMessageResponse, NewEmail,
                 NewEmailBuilder, Part, PartBuilder,
+                RawMessage,
             },
             rules::{
                 ApiRuleEndpoints, CreateRuleRequestBuilder,
+                DisableRuleRequestBuilder,
+                EnableRuleRequestBuilder,
                 ListRulesRequestBuilder, ListRulesResponse,
                 RuleBuilder, RuleRequestBuilder,
                 WebhookActionBuilder,
@@ -56,6 +108,13 @@ pub mod prelude {
             stats::*,
             ResponseStatus,
         },
-        client::Mailinator,
+        client::{
+            Mailinator, MailinatorBuilder, RateLimit,
+            RetryPolicy, RetryPolicyBuilder,
+        },
+        names::{
+            DomainName, DomainRef, InboxName, MessageId,
+        },
+        ApiError, Error,
     };
 }
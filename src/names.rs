@@ -0,0 +1,570 @@
+use crate::Error;
+use derive_builder::UninitializedFieldError;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+const SPECIAL_DOMAINS: &[&str] = &["private", "public"];
+
+fn invalid<T>(
+    kind: &str,
+    value: &str,
+    reason: &str,
+) -> Result<T, Error> {
+    Err(Error::Builder(format!(
+        "invalid {kind} {value:?}: {reason}"
+    )))
+}
+
+/// The local part and domain of an email address.
+fn split_address(
+    address: &str,
+) -> Result<(&str, &str), Error> {
+    let address = address.trim();
+    let address = address
+        .rsplit_once('<')
+        .map_or(address, |(_, a)| a.trim_end_matches('>'));
+    address.rsplit_once('@').map_or_else(
+        || invalid("email address", address, "missing @"),
+        Ok,
+    )
+}
+
+/// A Mailinator domain: `private`, `public` or a lower cased dns
+/// name.
+///
+/// ```rust
+/// use mailinator_rs::prelude::DomainName;
+///
+/// let domain = DomainName::try_from("Team.Testinator.com").unwrap();
+/// assert_eq!(domain.as_str(), "team.testinator.com");
+/// assert!(DomainName::try_from("private").unwrap().is_private());
+/// assert_eq!(
+///     DomainName::from_address("qa+1@example.com").unwrap().as_str(),
+///     "example.com"
+/// );
+/// assert!(DomainName::try_from("user@example.com").is_err());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct DomainName(String);
+
+impl DomainName {
+    /// The domain of an email address.
+    ///
+    /// # Errors
+    /// Returns [`Error::Builder`] when the address has no `@` or
+    /// its domain is invalid.
+    pub fn from_address(
+        address: &str,
+    ) -> Result<Self, Error> {
+        let (_, domain) = split_address(address)?;
+        Self::try_from(domain)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The team private domain.
+    #[must_use]
+    pub fn is_private(&self) -> bool {
+        self.0 == "private"
+    }
+
+    /// The public domain, shared by every Mailinator user.
+    #[must_use]
+    pub fn is_public(&self) -> bool {
+        self.0 == "public"
+    }
+}
+
+impl TryFrom<&str> for DomainName {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        const KIND: &str = "domain name";
+        let domain = value.trim().to_ascii_lowercase();
+        if SPECIAL_DOMAINS.contains(&domain.as_str()) {
+            return Ok(Self(domain));
+        }
+        if domain.contains('@') {
+            return invalid(
+                KIND,
+                value,
+                "looks like an email address, use DomainName::from_address",
+            );
+        }
+        if domain.is_empty() || domain.len() > 253 {
+            return invalid(
+                KIND,
+                value,
+                "expected 1 to 253 characters",
+            );
+        }
+        let labels: Vec<&str> = domain.split('.').collect();
+        if labels.len() < 2 {
+            return invalid(
+                KIND,
+                value,
+                "expected `private`, `public` or a dotted name",
+            );
+        }
+        for label in labels {
+            if label.is_empty() || label.len() > 63 {
+                return invalid(
+                    KIND,
+                    value,
+                    "labels must have 1 to 63 characters",
+                );
+            }
+            if label.starts_with('-')
+                || label.ends_with('-')
+            {
+                return invalid(
+                    KIND,
+                    value,
+                    "labels can not start or end with `-`",
+                );
+            }
+            if !label.chars().all(|c| {
+                c.is_ascii_alphanumeric() || c == '-'
+            }) {
+                return invalid(
+                    KIND,
+                    value,
+                    "unexpected character",
+                );
+            }
+        }
+        Ok(Self(domain))
+    }
+}
+
+/// A single Mailinator inbox, lower cased.
+///
+/// Inboxes hold letters, digits and `.`, `_`, `-` or `+`. Use
+/// [`InboxSelector`] to address several inboxes at once.
+///
+/// [`InboxSelector`]: crate::prelude::InboxSelector
+///
+/// ```rust
+/// use mailinator_rs::prelude::InboxName;
+///
+/// assert_eq!(InboxName::try_from("QA.Signup+42").unwrap().as_str(), "qa.signup+42");
+/// assert_eq!(
+///     InboxName::from_address("Jane <jane.doe@example.com>").unwrap().as_str(),
+///     "jane.doe"
+/// );
+/// assert!(InboxName::try_from("jane.doe@example.com").is_err());
+/// assert!(InboxName::try_from("qa-*").is_err());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct InboxName(String);
+
+impl InboxName {
+    /// The inbox of an email address, i.e. its local part.
+    ///
+    /// # Errors
+    /// Returns [`Error::Builder`] when the address has no `@` or
+    /// its local part is not a valid inbox.
+    pub fn from_address(
+        address: &str,
+    ) -> Result<Self, Error> {
+        let (local, _) = split_address(address)?;
+        Self::try_from(local)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for InboxName {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        const KIND: &str = "inbox name";
+        let inbox = value.trim().to_lowercase();
+        if inbox.contains('@') {
+            return invalid(
+                KIND,
+                value,
+                "looks like an email address, use InboxName::from_address",
+            );
+        }
+        if inbox.contains(['*', ',']) {
+            return invalid(
+                KIND,
+                value,
+                "wildcards and lists need an InboxSelector",
+            );
+        }
+        if inbox.is_empty() || inbox.len() > 64 {
+            return invalid(
+                KIND,
+                value,
+                "expected 1 to 64 characters",
+            );
+        }
+        if !inbox.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '.' | '_' | '-' | '+')
+        }) {
+            return invalid(
+                KIND,
+                value,
+                "unexpected character",
+            );
+        }
+        Ok(Self(inbox))
+    }
+}
+
+/// The id of a message, as listed in an inbox.
+///
+/// ```rust
+/// use mailinator_rs::prelude::MessageId;
+///
+/// assert!(MessageId::try_from("testinbox-1700000000-12345678").is_ok());
+/// assert!(MessageId::try_from("").is_err());
+/// assert!(MessageId::try_from("id/../other").is_err());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct MessageId(String);
+
+impl MessageId {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for MessageId {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        const KIND: &str = "message id";
+        let id = value.trim();
+        if id.is_empty() || id.len() > 256 {
+            return invalid(
+                KIND,
+                value,
+                "expected 1 to 256 characters",
+            );
+        }
+        if !id.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '.' | '_' | '-')
+        }) {
+            return invalid(
+                KIND,
+                value,
+                "unexpected character",
+            );
+        }
+        Ok(Self(id.to_owned()))
+    }
+}
+
+/// A domain as the domain and rule endpoints take it: a
+/// [`DomainName`] or the opaque id the api lists it under.
+///
+/// ```rust
+/// use mailinator_rs::prelude::{DomainName, DomainRef};
+///
+/// assert_eq!(
+///     DomainRef::try_from("Team.Example.com").unwrap(),
+///     DomainRef::Name(DomainName::try_from("team.example.com").unwrap())
+/// );
+/// assert_eq!(
+///     DomainRef::try_from("5f8D2a1c_77").unwrap(),
+///     DomainRef::Id(String::from("5f8D2a1c_77"))
+/// );
+/// assert!(DomainRef::try_from("qa@example.com").is_err());
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum DomainRef {
+    Name(DomainName),
+    /// An id kept as given, case included.
+    Id(String),
+}
+
+impl DomainRef {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Name(name) => name.as_str(),
+            Self::Id(id) => id,
+        }
+    }
+}
+
+impl TryFrom<&str> for DomainRef {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Ok(name) = DomainName::try_from(value) {
+            return Ok(Self::Name(name));
+        }
+        let id = value.trim();
+        if id.is_empty() || id.len() > 256 {
+            return invalid(
+                "domain",
+                value,
+                "expected a domain name or an id of 1 to 256 characters",
+            );
+        }
+        if !id.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '_' | '-')
+        }) {
+            return invalid(
+                "domain",
+                value,
+                "expected a domain name or an id of letters, digits, `_` and `-`",
+            );
+        }
+        Ok(Self::Id(id.to_owned()))
+    }
+}
+
+impl From<DomainName> for DomainRef {
+    fn from(name: DomainName) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl From<DomainRef> for NameInput {
+    fn from(domain: DomainRef) -> Self {
+        Self(Some(domain.into()))
+    }
+}
+
+impl TryFrom<String> for DomainRef {
+    type Error = Error;
+
+    fn try_from(
+        value: String,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl FromStr for DomainRef {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl AsRef<str> for DomainRef {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for DomainRef {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<DomainRef> for String {
+    fn from(domain: DomainRef) -> Self {
+        match domain {
+            DomainRef::Name(name) => name.into(),
+            DomainRef::Id(id) => id,
+        }
+    }
+}
+
+/// Conversions shared by the three names.
+macro_rules! name_conversions {
+    ($($name:ident),*) => {$(
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::try_from(value.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::try_from(s)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(name: $name) -> Self {
+                name.0
+            }
+        }
+
+        impl From<$name> for NameInput {
+            fn from(name: $name) -> Self {
+                Self(Some(name.0))
+            }
+        }
+    )*};
+}
+
+name_conversions!(DomainName, InboxName, MessageId);
+
+/// A name given to a request builder, validated when the request
+/// is built.
+#[derive(Debug, Clone, Default)]
+pub struct NameInput(Option<String>);
+
+impl NameInput {
+    /// Validates a mandatory field.
+    pub fn required<T>(
+        &self,
+        field: &'static str,
+    ) -> Result<T, Error>
+    where
+        T: for<'a> TryFrom<&'a str, Error = Error>,
+    {
+        self.0.as_deref().map_or_else(
+            || {
+                Err(UninitializedFieldError::new(field)
+                    .into())
+            },
+            T::try_from,
+        )
+    }
+}
+
+impl From<String> for NameInput {
+    fn from(value: String) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl From<&str> for NameInput {
+    fn from(value: &str) -> Self {
+        Self(Some(value.to_owned()))
+    }
+}
+
+/// An optional name given to a request builder, validated when the
+/// request is built.
+#[derive(Debug, Clone, Default)]
+pub struct OptionalNameInput {
+    set: bool,
+    value: Option<String>,
+}
+
+impl OptionalNameInput {
+    /// Validates a field that must be set, possibly to `None`.
+    pub fn required<T>(
+        &self,
+        field: &'static str,
+    ) -> Result<Option<T>, Error>
+    where
+        T: for<'a> TryFrom<&'a str, Error = Error>,
+    {
+        if !self.set {
+            return Err(UninitializedFieldError::new(
+                field,
+            )
+            .into());
+        }
+        self.optional()
+    }
+
+    /// Validates a field left to `None` when unset.
+    pub fn optional<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: for<'a> TryFrom<&'a str, Error = Error>,
+    {
+        self.value.as_deref().map(T::try_from).transpose()
+    }
+}
+
+impl From<Option<String>> for OptionalNameInput {
+    fn from(value: Option<String>) -> Self {
+        Self { set: true, value }
+    }
+}
+
+impl From<String> for OptionalNameInput {
+    fn from(value: String) -> Self {
+        Some(value).into()
+    }
+}
+
+impl From<&str> for OptionalNameInput {
+    fn from(value: &str) -> Self {
+        Some(value.to_owned()).into()
+    }
+}
+
+impl From<InboxName> for OptionalNameInput {
+    fn from(name: InboxName) -> Self {
+        Some(name.0).into()
+    }
+}
@@ -1,4 +1,133 @@
+use crate::Error;
+use percent_encoding::{
+    utf8_percent_encode, AsciiSet, CONTROLS,
+};
+use serde::Serialize;
+
 pub trait AsUrl {
     #[allow(clippy::wrong_self_convention)]
-    fn as_url_path(self) -> String;
+    fn as_url_path(self) -> Result<String, Error>;
+}
+
+/// Characters escaped in a path segment or query value, everything
+/// but the unreserved set of RFC 3986.
+const COMPONENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'\'')
+    .add(b'*')
+    .add(b'+')
+    .add(b',')
+    .add(b'/')
+    .add(b':')
+    .add(b';')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'?')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Percent-encodes a single path segment.
+pub fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, COMPONENT).to_string()
+}
+
+/// An api path built segment by segment, user supplied segments
+/// and query values being percent-encoded.
+#[derive(Debug, Clone)]
+pub struct ApiPath {
+    path: String,
+    query: Vec<String>,
+}
+
+impl ApiPath {
+    /// Starts at `/api/v2`.
+    pub fn v2() -> Self {
+        Self {
+            path: String::from("/api/v2"),
+            query: Vec::new(),
+        }
+    }
+
+    /// Appends an encoded segment.
+    pub fn segment(
+        mut self,
+        segment: impl AsRef<str>,
+    ) -> Self {
+        self.path.push('/');
+        self.path
+            .push_str(&encode_segment(segment.as_ref()));
+        self
+    }
+
+    /// Appends a segment that is already encoded, or holds api
+    /// syntax such as `*`.
+    pub fn raw_segment(mut self, segment: &str) -> Self {
+        self.path.push('/');
+        self.path.push_str(segment);
+        self
+    }
+
+    pub fn trailing_slash(mut self) -> Self {
+        self.path.push('/');
+        self
+    }
+
+    /// Appends an encoded query parameter.
+    pub fn query_pair(
+        mut self,
+        name: &str,
+        value: impl AsRef<str>,
+    ) -> Self {
+        self.query.push(format!(
+            "{}={}",
+            encode_segment(name),
+            encode_segment(value.as_ref())
+        ));
+        self
+    }
+
+    /// Appends the fields of `params` as query parameters.
+    ///
+    /// # Errors
+    /// Returns [`Error::Builder`] when `params` can not be
+    /// serialized into a query string.
+    pub fn query<T: Serialize>(
+        mut self,
+        params: &T,
+    ) -> Result<Self, Error> {
+        let query =
+            serde_qs::to_string(params).map_err(|e| {
+                Error::Builder(format!(
+                    "invalid query parameters: {e}"
+                ))
+            })?;
+        if !query.is_empty() {
+            self.query.push(query);
+        }
+        Ok(self)
+    }
+
+    pub fn build(self) -> String {
+        if self.query.is_empty() {
+            self.path
+        } else {
+            format!(
+                "{}?{}",
+                self.path,
+                self.query.join("&")
+            )
+        }
+    }
 }
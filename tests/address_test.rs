@@ -0,0 +1,96 @@
+use mailinator_rs::prelude::{Email, Mailbox, Msg};
+use serde_json::json;
+
+#[test]
+fn parses_display_names_and_tags() {
+    let quoted = Mailbox::parse(
+        r#""Doe, John \"JD\"" <john.doe+news@Example.COM>"#,
+    )
+    .unwrap();
+    assert_eq!(
+        quoted.display_name.as_deref(),
+        Some(r#"Doe, John "JD""#)
+    );
+    assert_eq!(quoted.local_part, "john.doe+news");
+    assert_eq!(quoted.domain, "example.com");
+    assert_eq!(quoted.tag(), Some("news"));
+    assert_eq!(quoted.base_local_part(), "john.doe");
+    assert_eq!(quoted.normalized(), "john.doe@example.com");
+
+    let encoded = Mailbox::parse(
+        "=?ISO-8859-1?Q?J=F6rg?= =?utf-8?B?U2NobWl0dA==?= <jorg@example.de>",
+    )
+    .unwrap();
+    assert_eq!(
+        encoded.display_name.as_deref(),
+        Some("JörgSchmitt")
+    );
+
+    let comment =
+        Mailbox::parse("ops@example.com (Ops Team)")
+            .unwrap();
+    assert_eq!(
+        comment.display_name.as_deref(),
+        Some("Ops Team")
+    );
+    assert_eq!(
+        comment.to_string(),
+        r#""Ops Team" <ops@example.com>"#
+    );
+
+    assert_eq!(Mailbox::parse("Just a name"), None);
+}
+
+#[test]
+fn parses_address_lists() {
+    let list = Mailbox::parse_list(
+        r#""Smith, Ann" <ann@example.com>, bob@example.com, undisclosed"#,
+    );
+    let addresses: Vec<String> =
+        list.iter().map(Mailbox::address).collect();
+    assert_eq!(
+        addresses,
+        ["ann@example.com", "bob@example.com"]
+    );
+}
+
+#[test]
+fn reads_sender_and_recipients() {
+    let email: Email = serde_json::from_value(json!({
+        "from": "Our App",
+        "fromfull": "NoReply+welcome@ourapp.com",
+        "to": "testinbox@example.com",
+        "parts": [],
+    }))
+    .unwrap();
+    let sender = email.sender().unwrap();
+    assert_eq!(
+        sender.display_name.as_deref(),
+        Some("Our App")
+    );
+    assert!(sender.matches("noreply@ourapp.com"));
+    assert_eq!(
+        email.recipients()[0].address(),
+        "testinbox@example.com"
+    );
+
+    let msg = Msg {
+        subject: None,
+        domain: Some(String::from("example.com")),
+        from: Some(String::from(
+            "Our App <noreply@ourapp.com>",
+        )),
+        id: None,
+        to: Some(String::from("testinbox")),
+        time: None,
+        seconds_ago: None,
+    };
+    assert!(msg
+        .sender()
+        .unwrap()
+        .matches("NOREPLY@ourapp.com"));
+    assert_eq!(
+        msg.recipient().unwrap().address(),
+        "testinbox@example.com"
+    );
+}
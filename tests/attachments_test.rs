@@ -0,0 +1,274 @@
+mod common;
+
+use common::{serve_replies, serve_sequence, Reply};
+use mailinator_rs::prelude::{
+    ApiMessageEndpoints, AttachmentKey,
+    FetchAllAttachmentsRequestBuilder,
+    FetchAttachmentRequestBuilder, LookupField, Mailinator,
+};
+use serde_json::json;
+
+fn client(url: &str) -> Mailinator {
+    Mailinator::builder()
+        .api_url(url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap()
+}
+
+fn listing(attachments: serde_json::Value) -> String {
+    json!({ "attachments": attachments }).to_string()
+}
+
+fn attachment_request(
+    attachment: LookupField,
+) -> FetchAttachmentRequestBuilder {
+    let mut builder =
+        FetchAttachmentRequestBuilder::default();
+    builder
+        .domain("private")
+        .inbox("qa")
+        .message_id("m-1")
+        .attachment(attachment);
+    builder
+}
+
+#[tokio::test]
+async fn streams_attachment_with_rfc2231_filename() {
+    let data = "0123456789ß".repeat(10_000);
+    let (url, targets) = serve_replies(vec![Reply::ok(
+        data.clone(),
+    )
+    .header("content-type", "application/pdf")
+    .header(
+        "content-disposition",
+        "attachment; filename=\"fallback.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%20final.pdf",
+    )]);
+
+    let mut written = Vec::new();
+    let info = client(&url)
+        .download_attachment(
+            attachment_request(LookupField::Id(3))
+                .build()
+                .unwrap(),
+            &mut written,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        info.filename.as_deref(),
+        Some("résumé final.pdf")
+    );
+    assert_eq!(
+        info.content_type.as_deref(),
+        Some("application/pdf")
+    );
+    assert_eq!(info.size, data.len() as u64);
+    assert_eq!(written, data.as_bytes());
+    assert_eq!(
+        *targets.lock().unwrap(),
+        ["/api/v2/domains/private/inboxes/qa/messages/m-1/attachments/3"]
+    );
+}
+
+#[tokio::test]
+async fn saves_fetched_attachment_bytes() {
+    let (url, targets) = serve_replies(vec![
+        Reply::ok(String::from("a,b\r\n1,2\r\n"))
+            .header("content-type", "text/csv")
+            .header(
+                "content-disposition",
+                "attachment; filename=\"data.csv\"",
+            ),
+        Reply::ok(String::from("plain"))
+            .header("content-type", "text/plain"),
+    ]);
+    let client = client(&url);
+
+    let file = client
+        .fetch_attachment(
+            attachment_request(LookupField::Id(1))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(file.filename.as_deref(), Some("data.csv"));
+    assert_eq!(
+        file.content_type.as_deref(),
+        Some("text/csv")
+    );
+    let path = std::env::temp_dir().join(format!(
+        "mailinator-save-{}.csv",
+        std::process::id()
+    ));
+    file.save_to(&path).await.unwrap();
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        b"a,b\r\n1,2\r\n"
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    // Without content-disposition the requested name is kept.
+    let file = client
+        .fetch_attachment(
+            attachment_request(LookupField::Name(
+                String::from("my notes.txt"),
+            ))
+            .build()
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        file.filename.as_deref(),
+        Some("my notes.txt")
+    );
+    assert_eq!(file.data, b"plain");
+    assert_eq!(
+        targets.lock().unwrap()[1],
+        "/api/v2/domains/private/inboxes/qa/messages/m-1/attachments/my%20notes.txt"
+    );
+}
+
+#[tokio::test]
+async fn keys_attachments_without_id_by_position() {
+    let (url, targets) = serve_sequence(vec![
+        listing(json!([
+            {"attachment-id": 1, "filename": "a.txt"},
+            {"filename": "b.txt"},
+            {"content-type": "text/plain"},
+            {"filename": ""},
+        ])),
+        String::from("first"),
+        String::from("second"),
+    ]);
+
+    let files = client(&url)
+        .fetch_all_attachments(
+            FetchAllAttachmentsRequestBuilder::default()
+                .domain("private")
+                .inbox("qa")
+                .message_id("m-1")
+                .concurrency(1)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let attachments =
+        "/api/v2/domains/private/inboxes/qa/messages/m-1/attachments";
+    assert_eq!(
+        *targets.lock().unwrap(),
+        [
+            attachments.to_owned(),
+            format!("{attachments}/1"),
+            format!("{attachments}/b.txt"),
+        ]
+    );
+    assert_eq!(files.len(), 2);
+    let listed = files.get(1).unwrap();
+    assert_eq!(
+        listed.file.filename.as_deref(),
+        Some("a.txt")
+    );
+    assert_eq!(listed.file.data, b"first");
+    let unlisted =
+        files.get_key(AttachmentKey::Position(1)).unwrap();
+    assert_eq!(
+        unlisted.file.filename.as_deref(),
+        Some("b.txt")
+    );
+    assert_eq!(unlisted.file.data, b"second");
+    assert_eq!(unlisted.key, AttachmentKey::Position(1));
+}
+
+#[tokio::test]
+async fn writes_safe_and_unique_file_names() {
+    let names = [
+        "../etc/passwd",
+        "..\\x",
+        "/abs/dir/report.pdf",
+        "C:\\Windows\\evil.exe",
+        "",
+        "...",
+        "\u{1}\u{7}\u{7f}",
+        "report.pdf",
+        "report.pdf",
+        "a<b>:c?.txt",
+    ];
+    let (url, _) = serve_sequence(vec![
+        listing(json!(names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| json!({
+                "attachment-id": id,
+                "filename": name,
+            }))
+            .collect::<Vec<_>>())),
+        String::from("data"),
+    ]);
+    let dir = std::env::temp_dir().join(format!(
+        "mailinator-attachments-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("passwd"), "existing").unwrap();
+
+    let files = client(&url)
+        .fetch_all_attachments(
+            FetchAllAttachmentsRequestBuilder::default()
+                .domain("private")
+                .inbox("qa")
+                .message_id("m-1")
+                .directory(Some(dir.clone()))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let written: Vec<String> = (0..names.len() as u64)
+        .map(|id| {
+            let path = files
+                .get(id)
+                .unwrap()
+                .path
+                .clone()
+                .unwrap();
+            assert_eq!(path.parent(), Some(dir.as_path()));
+            assert_eq!(
+                std::fs::read(&path).unwrap(),
+                b"data"
+            );
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into()
+        })
+        .collect();
+    assert_eq!(
+        written,
+        [
+            "passwd (1)",
+            "x",
+            "report.pdf",
+            "evil.exe",
+            "attachment-4",
+            "attachment-5",
+            "attachment-6",
+            "report (1).pdf",
+            "report (2).pdf",
+            "a_b__c_.txt",
+        ]
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("passwd"))
+            .unwrap(),
+        "existing"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{inbox, serve_sequence};
+use mailinator_rs::prelude::{
+    AwaitMessageRequestBuilder, Error, Mailinator,
+    MessageFilter,
+};
+use std::time::Duration;
+
+fn request() -> AwaitMessageRequestBuilder {
+    let mut builder = AwaitMessageRequestBuilder::default();
+    builder.domain("private").inbox("qa");
+    builder
+}
+
+#[test]
+fn rejects_invalid_poll_settings() {
+    assert!(request().build().is_ok());
+    for backoff in [
+        0.5,
+        -1.0,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        assert!(
+            request().backoff(backoff).build().is_err(),
+            "{backoff}"
+        );
+    }
+    assert!(request().backoff(1.0).build().is_ok());
+    assert!(request()
+        .poll_interval(Duration::ZERO)
+        .build()
+        .is_err());
+    assert!(request()
+        .poll_interval(Duration::from_secs(30))
+        .build()
+        .is_err());
+    assert!(request()
+        .poll_interval(Duration::from_secs(30))
+        .max_poll_interval(Duration::from_secs(30))
+        .build()
+        .is_ok());
+}
+
+#[tokio::test(start_paused = true)]
+async fn times_out_with_every_message_seen() {
+    let (url, targets) = serve_sequence(vec![
+        inbox(&[("a", 1)]),
+        inbox(&[("b", 2), ("a", 1)]),
+        inbox(&[("b", 2)]),
+    ]);
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+
+    let err = client
+        .await_message(
+            request()
+                .filter(
+                    MessageFilter::default()
+                        .subject_contains("never"),
+                )
+                .poll_interval(Duration::from_secs(2))
+                .max_poll_interval(Duration::from_secs(4))
+                .backoff(2.0)
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap_err();
+
+    let Error::Timeout { elapsed, seen } = err else {
+        panic!("expected a timeout, got {err}");
+    };
+    assert!(elapsed >= Duration::from_secs(10));
+    let ids: Vec<_> = seen
+        .iter()
+        .filter_map(|m| m.id.as_deref())
+        .collect();
+    assert_eq!(ids, ["a", "b"]);
+    // Polls at 0, 2, 6 and 10 seconds.
+    assert_eq!(targets.lock().unwrap().len(), 4);
+}
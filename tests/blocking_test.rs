@@ -0,0 +1,140 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::{inbox, serve_replies, Reply};
+use mailinator_rs::{
+    blocking,
+    prelude::{
+        Error, FetchAttachmentRequestBuilder,
+        FetchInboxRequestBuilder, LookupField, Mailinator,
+    },
+};
+
+fn client(url: &str) -> blocking::Mailinator {
+    blocking::Mailinator::from_async(
+        Mailinator::builder()
+            .api_url(url)
+            .api_token("XXXXX-XXXXXX")
+            .build()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+fn attachment_request() -> FetchAttachmentRequestBuilder {
+    let mut builder =
+        FetchAttachmentRequestBuilder::default();
+    builder
+        .domain("private")
+        .inbox("qa")
+        .message_id("m-1")
+        .attachment(LookupField::Id(7));
+    builder
+}
+
+#[test]
+fn fetches_json() {
+    let (url, targets) =
+        serve_replies(vec![Reply::ok(inbox(&[
+            ("a", 1),
+            ("b", 2),
+        ]))]);
+
+    let response = client(&url)
+        .fetch_inbox(
+            FetchInboxRequestBuilder::default()
+                .domain(String::from("private"))
+                .inbox("qa")
+                .query_params(None)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    let ids: Vec<_> = response
+        .msgs
+        .iter()
+        .map(|m| m.id.as_deref())
+        .collect();
+    assert_eq!(ids, [Some("a"), Some("b")]);
+    assert_eq!(
+        *targets.lock().unwrap(),
+        ["/api/v2/domains/private/inboxes/qa"]
+    );
+}
+
+#[test]
+fn downloads_binary_attachments() {
+    let data = "%PDF-\u{0}\u{1}binary";
+    let reply = || {
+        Reply::ok(data.to_owned())
+            .header("content-type", "application/pdf")
+            .header(
+                "content-disposition",
+                "attachment; filename=\"report.pdf\"",
+            )
+    };
+    let (url, targets) =
+        serve_replies(vec![reply(), reply()]);
+    let client = client(&url);
+
+    let file = client
+        .fetch_attachment(
+            attachment_request().build().unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        file.filename.as_deref(),
+        Some("report.pdf")
+    );
+    assert_eq!(
+        file.content_type.as_deref(),
+        Some("application/pdf")
+    );
+    assert_eq!(file.data, data.as_bytes());
+
+    let mut written = Vec::new();
+    let info = client
+        .download_attachment(
+            attachment_request().build().unwrap(),
+            &mut written,
+        )
+        .unwrap();
+    assert_eq!(written, data.as_bytes());
+    assert_eq!(info.size, data.len() as u64);
+    assert_eq!(
+        info.filename.as_deref(),
+        Some("report.pdf")
+    );
+    assert_eq!(
+        targets.lock().unwrap()[1],
+        "/api/v2/domains/private/inboxes/qa/messages/m-1/attachments/7"
+    );
+}
+
+#[test]
+fn surfaces_api_errors() {
+    let (url, _) = serve_replies(vec![
+        Reply::status(404),
+        Reply::status(400),
+    ]);
+    let client = client(&url);
+
+    let err = client
+        .fetch_attachment(
+            attachment_request().build().unwrap(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)), "{err:?}");
+
+    let mut written = Vec::new();
+    let err = client
+        .download_attachment(
+            attachment_request().build().unwrap(),
+            &mut written,
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::Http(_)), "{err:?}");
+    assert!(written.is_empty());
+}
@@ -0,0 +1,36 @@
+use mailinator_rs::prelude::{Error, Mailinator};
+use std::time::Duration;
+
+#[test]
+fn builds_with_transport_options() {
+    let client = Mailinator::builder()
+        .api_url("https://mailinator.com/")
+        .api_token("XXXXX-XXXXXX")
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(30))
+        .user_agent("qa-suite/1.0")
+        .https_proxy("http://127.0.0.1:3128")
+        .build();
+    assert!(client.is_ok());
+}
+
+#[test]
+fn rejects_invalid_api_url() {
+    let err = Mailinator::builder()
+        .api_url("not a url")
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Builder(_)));
+}
+
+#[test]
+fn rejects_transport_options_with_custom_client() {
+    let err = Mailinator::builder()
+        .api_token("XXXXX-XXXXXX")
+        .client(reqwest::Client::new())
+        .timeout(Duration::from_secs(1))
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Builder(_)));
+}
@@ -0,0 +1,134 @@
+// Each test binary uses only part of the fixtures.
+#![allow(dead_code)]
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// A canned answer of the fixture server.
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Reply {
+    pub fn ok(body: String) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn header(
+        mut self,
+        name: &'static str,
+        value: impl Into<String>,
+    ) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// Answers each request with the next of `bodies`, repeating the
+/// last one, and records the request targets.
+pub fn serve_sequence(
+    bodies: Vec<String>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    serve_replies(
+        bodies.into_iter().map(Reply::ok).collect(),
+    )
+}
+
+/// Answers each request with the next of `replies`, repeating the
+/// last one, and records the request targets.
+pub fn serve_replies(
+    replies: Vec<Reply>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener =
+        TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}",
+        listener.local_addr().unwrap()
+    );
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&targets);
+    thread::spawn(move || {
+        for (n, stream) in listener.incoming().enumerate() {
+            let mut stream = stream.unwrap();
+            let mut reader =
+                BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            seen.lock().unwrap().push(
+                request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap()
+                    .to_owned(),
+            );
+            let reply = &replies[n.min(replies.len() - 1)];
+            let headers: String = reply
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    format!("{name}: {value}\r\n")
+                })
+                .collect();
+            let content_type =
+                if reply.headers.iter().any(|(name, _)| {
+                    name.eq_ignore_ascii_case(
+                        "content-type",
+                    )
+                }) {
+                    ""
+                } else {
+                    "content-type: application/json\r\n"
+                };
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\n{content_type}content-length: {}\r\nconnection: close\r\n{headers}\r\n{}",
+                reply.status,
+                reply.body.len(),
+                reply.body
+            )
+            .unwrap();
+        }
+    });
+    (url, targets)
+}
+
+/// An inbox listing holding messages `(id, time)`.
+pub fn inbox(msgs: &[(&str, u64)]) -> String {
+    serde_json::json!({
+        "domain": "private",
+        "to": "qa",
+        "msgs": msgs
+            .iter()
+            .map(|(id, time)| serde_json::json!({
+                "id": id,
+                "subject": format!("Message {id}"),
+                "from": "noreply@example.com",
+                "to": "qa",
+                "time": time,
+            }))
+            .collect::<Vec<_>>(),
+    })
+    .to_string()
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::serve_sequence;
+use mailinator_rs::prelude::{
+    ApiDomainEndpoints, ApiRuleEndpoints,
+    CreateRuleRequestBuilder, DisableRuleRequestBuilder,
+    DomainRequestBuilder, EnableRuleRequestBuilder,
+    ListRulesRequestBuilder, Mailinator, RuleBuilder,
+    RuleRequestBuilder,
+};
+
+const ID: &str = "5f8D2a1c77e0";
+
+#[tokio::test]
+async fn domain_ids_reach_every_domain_and_rule_endpoint() {
+    let (url, targets) =
+        serve_sequence(vec![String::from("{}")]);
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+    let rule = || {
+        RuleRequestBuilder::default()
+            .domain_id(ID)
+            .rule_id(String::from("r-1"))
+            .build()
+            .unwrap()
+    };
+
+    // Only the requested paths matter, not the fixture answers.
+    let _ = client
+        .get_domain(
+            DomainRequestBuilder::default()
+                .domain(ID)
+                .build()
+                .unwrap(),
+        )
+        .await;
+    let _ = client
+        .create_rule(
+            CreateRuleRequestBuilder::default()
+                .domain(ID)
+                .build()
+                .unwrap(),
+            RuleBuilder::default()
+                ._id(None)
+                .name(String::from("drop"))
+                .description(None)
+                .enabled(None)
+                .priority(1)
+                .conditions(Vec::new())
+                .actions(Vec::new())
+                .build()
+                .unwrap(),
+        )
+        .await;
+    let _ = client
+        .enable_rule(
+            EnableRuleRequestBuilder::default()
+                .domain_id(ID)
+                .rule_id(String::from("r-1"))
+                .build()
+                .unwrap(),
+        )
+        .await;
+    let _ = client
+        .disable_rule(
+            DisableRuleRequestBuilder::default()
+                .domain_id(ID)
+                .rule_id(String::from("r-1"))
+                .build()
+                .unwrap(),
+        )
+        .await;
+    let _ = client
+        .list_rules(
+            ListRulesRequestBuilder::default()
+                .domain_id(ID)
+                .build()
+                .unwrap(),
+        )
+        .await;
+    let _ = client.get_rule(rule()).await;
+
+    let domain = format!("/api/v2/domains/{ID}");
+    assert_eq!(
+        *targets.lock().unwrap(),
+        [
+            domain.clone(),
+            format!("{domain}/rules/"),
+            format!("{domain}/rules/r-1?action=enable"),
+            format!("{domain}/rules/r-1?action=disable"),
+            format!("{domain}/rules/"),
+            format!("{domain}/rules/r-1"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn lists_domains_under_the_api_prefix() {
+    let (url, targets) =
+        serve_sequence(vec![String::from(
+            r#"{"domains": []}"#,
+        )]);
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+
+    let response = client.get_all_domains().await.unwrap();
+    assert!(response.domains.is_empty());
+    assert_eq!(
+        *targets.lock().unwrap(),
+        ["/api/v2/domains/"]
+    );
+}
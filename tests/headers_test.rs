@@ -0,0 +1,88 @@
+use mailinator_rs::prelude::{Email, Headers};
+use serde_json::json;
+
+fn email() -> Email {
+    serde_json::from_value(json!({
+        "subject": "=?UTF-8?B?UmVzZXQgeW91ciBwYXNzd29yZA==?=",
+        "headers": {
+            "From": "=?UTF-8?Q?S=C3=A9bastien?= <seb@example.fr>",
+            "received": ["from mx1.example.fr", "from mx2.example.fr"],
+            "x-priority": 3
+        },
+        "parts": [
+            {
+                "headers": {
+                    "content-type": "application/pdf",
+                    "content-disposition": "attachment; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf"
+                },
+                "body": "JVBERi0="
+            },
+            {
+                "headers": {
+                    "content-type": "text/plain",
+                    "content-disposition": "attachment; filename*0*=utf-8''na%C3%AF; filename*1=\"ve notes.txt\""
+                },
+                "body": "notes"
+            },
+            {
+                "headers": {
+                    "content-type": "image/png; name=\"=?utf-8?Q?caf=C3=A9.png?=\""
+                },
+                "body": "iVBORw=="
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn keeps_multi_valued_headers() {
+    let email = email();
+    let headers = email.headers.as_ref().unwrap();
+
+    assert_eq!(
+        headers.get_all("Received").collect::<Vec<_>>(),
+        ["from mx1.example.fr", "from mx2.example.fr"]
+    );
+    assert_eq!(email.header("X-PRIORITY"), Some("3"));
+
+    let round_trip: Headers = serde_json::from_value(
+        serde_json::to_value(headers).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(&round_trip, headers);
+}
+
+#[test]
+fn decodes_encoded_words() {
+    let email = email();
+
+    assert_eq!(
+        email.decoded_subject().as_deref(),
+        Some("Reset your password")
+    );
+    assert_eq!(
+        email.header_decoded("from").as_deref(),
+        Some("Sébastien <seb@example.fr>")
+    );
+    assert_eq!(
+        email.sender().unwrap().display_name.as_deref(),
+        Some("Sébastien")
+    );
+}
+
+#[test]
+fn decodes_rfc_2231_filenames() {
+    let email = email();
+    let names: Vec<_> =
+        email.parts.iter().map(|p| p.filename()).collect();
+
+    assert_eq!(
+        names,
+        [
+            Some(String::from("résumé.pdf")),
+            Some(String::from("naïve notes.txt")),
+            Some(String::from("café.png")),
+        ]
+    );
+}
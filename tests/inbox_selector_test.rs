@@ -0,0 +1,134 @@
+use mailinator_rs::prelude::{
+    AwaitMessageRequestBuilder, FetchInboxRequestBuilder,
+    InboxName, InboxSelector, PaginateInboxRequestBuilder,
+    WatchInboxRequestBuilder,
+};
+
+fn name(n: &str) -> InboxName {
+    InboxName::try_from(n).unwrap()
+}
+
+#[test]
+fn parses_api_syntax() {
+    assert_eq!(
+        InboxSelector::try_from("*").unwrap(),
+        InboxSelector::All
+    );
+    assert_eq!(
+        InboxSelector::try_from("alice, Bob").unwrap(),
+        InboxSelector::List(vec![
+            name("alice"),
+            name("bob")
+        ])
+    );
+    assert_eq!(
+        InboxSelector::try_from(String::from("qa-*"))
+            .unwrap(),
+        InboxSelector::Prefix(name("qa-"))
+    );
+    assert_eq!(
+        "testinbox"
+            .parse::<InboxSelector>()
+            .unwrap()
+            .as_single(),
+        Some("testinbox")
+    );
+}
+
+#[test]
+fn rejects_invalid_names() {
+    for bad in [
+        "",
+        "qa@example.com",
+        "qa inbox",
+        "alice,b/c",
+        "q/a*",
+        ",",
+    ] {
+        assert!(
+            InboxSelector::try_from(bad).is_err(),
+            "{bad:?}"
+        );
+    }
+    assert!(InboxSelector::try_from(Vec::<String>::new())
+        .is_err());
+}
+
+#[test]
+fn encodes_names_but_not_separators() {
+    let selector = InboxSelector::List(vec![
+        name("team+qa"),
+        name("a.b"),
+    ]);
+    assert_eq!(selector.to_path_segment(), "team%2Bqa,a.b");
+    assert_eq!(
+        InboxSelector::Prefix(name("qa+run"))
+            .to_path_segment(),
+        "qa%2Brun*"
+    );
+    assert_eq!(InboxSelector::All.to_path_segment(), "*");
+}
+
+#[test]
+fn builder_accepts_strings_and_selectors() {
+    assert!(FetchInboxRequestBuilder::default()
+        .domain(String::from("private"))
+        .inbox("qa-*")
+        .query_params(None)
+        .build()
+        .is_ok());
+    assert!(FetchInboxRequestBuilder::default()
+        .domain(String::from("private"))
+        .inbox(InboxSelector::All)
+        .query_params(None)
+        .build()
+        .is_ok());
+    assert!(FetchInboxRequestBuilder::default()
+        .domain(String::from("private"))
+        .inbox(vec![
+            String::from("alice"),
+            String::from("bob")
+        ])
+        .query_params(None)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn builders_reject_invalid_inboxes() {
+    for bad in ["user@domain.com", "", "a b"] {
+        assert!(
+            FetchInboxRequestBuilder::default()
+                .domain("private")
+                .inbox(bad)
+                .query_params(None)
+                .build()
+                .is_err(),
+            "{bad:?}"
+        );
+        assert!(AwaitMessageRequestBuilder::default()
+            .domain("private")
+            .inbox(bad)
+            .build()
+            .is_err());
+        assert!(WatchInboxRequestBuilder::default()
+            .domain("private")
+            .inbox(bad)
+            .build()
+            .is_err());
+        assert!(PaginateInboxRequestBuilder::default()
+            .domain("private")
+            .inbox(bad)
+            .build()
+            .is_err());
+    }
+    assert!(FetchInboxRequestBuilder::default()
+        .domain("private")
+        .query_params(None)
+        .build()
+        .is_err());
+    assert!(WatchInboxRequestBuilder::default()
+        .domain("private")
+        .build()
+        .is_ok());
+}
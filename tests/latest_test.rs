@@ -0,0 +1,97 @@
+mod common;
+
+use common::{inbox, serve_sequence};
+use mailinator_rs::prelude::{
+    Email, FetchMode, LatestCursor,
+    LatestMessagesRequestBuilder, Mailinator,
+};
+use serde_json::json;
+
+fn email(id: Option<&str>, time: Option<u64>) -> Email {
+    serde_json::from_value(
+        json!({"id": id, "time": time, "parts": []}),
+    )
+    .unwrap()
+}
+
+#[test]
+fn cursor_breaks_time_ties_by_id() {
+    let mut cursor = LatestCursor::default();
+    cursor.advance(&[
+        email(Some("a"), Some(1_000)),
+        email(Some("b"), Some(2_000)),
+        email(Some("c"), Some(2_000)),
+    ]);
+
+    assert!(!cursor.is_new(Some("a"), Some(1_000)));
+    assert!(!cursor.is_new(Some("b"), Some(2_000)));
+    assert!(!cursor.is_new(Some("c"), Some(2_000)));
+    assert!(cursor.is_new(Some("d"), Some(2_000)));
+    assert!(cursor.is_new(Some("e"), Some(2_001)));
+    assert!(!cursor.is_new(Some("f"), Some(1_999)));
+
+    cursor.advance(&[email(Some("d"), Some(2_000))]);
+    assert!(!cursor.is_new(Some("d"), Some(2_000)));
+    cursor.advance(&[email(Some("e"), Some(3_000))]);
+    assert!(cursor.is_new(Some("d"), Some(3_000)));
+}
+
+#[test]
+fn cursor_handles_missing_time_and_id() {
+    let mut cursor = LatestCursor::default();
+    assert!(cursor.is_fresh());
+    assert!(cursor.is_new(None, None));
+    assert!(cursor.is_new(None, Some(0)));
+    assert!(cursor.is_new(Some("u"), None));
+
+    cursor.advance(&[
+        email(Some("u"), None),
+        email(None, Some(1_000)),
+    ]);
+    assert!(!cursor.is_fresh());
+    // Undated messages are tracked by id.
+    assert!(!cursor.is_new(Some("u"), None));
+    assert!(cursor.is_new(Some("v"), None));
+    // Id-less messages are only new when strictly newer.
+    assert!(!cursor.is_new(None, Some(1_000)));
+    assert!(cursor.is_new(None, Some(1_001)));
+    assert!(!cursor.is_new(None, None));
+
+    cursor.advance(&[email(Some("v"), None)]);
+    assert!(!cursor.is_new(Some("v"), None));
+    assert!(!cursor.is_new(Some("u"), None));
+}
+
+#[tokio::test]
+async fn fan_out_fetches_bodies_from_the_selected_inbox() {
+    let body =
+        json!({"id": "a", "time": 2, "subject": "Hi", "parts": []})
+            .to_string();
+    let (url, targets) =
+        serve_sequence(vec![inbox(&[("a", 2)]), body]);
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+
+    let latest = client
+        .fetch_latest(
+            LatestMessagesRequestBuilder::default()
+                .domain("private")
+                .inbox(String::from("qa"))
+                .mode(FetchMode::FanOut { concurrency: 2 })
+                .limit(10_usize)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(latest.msgs.len(), 1);
+    assert!(!latest.cursor.is_new(Some("a"), Some(2)));
+    assert_eq!(
+        targets.lock().unwrap()[1],
+        "/api/v2/domains/private/inboxes/qa/messages/a"
+    );
+}
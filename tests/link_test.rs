@@ -0,0 +1,358 @@
+mod common;
+
+use common::serve_sequence;
+use mailinator_rs::prelude::{
+    CheckLinksRequestBuilder, Email, Error,
+    FetchLinkResponse, LinkKind, LinkPattern, Mailinator,
+};
+
+use reqwest::StatusCode;
+use serde_json::json;
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Serves the links of a message and a redirect chain:
+/// `/start` -> `/middle` -> `/end`, and `/loop` redirecting to
+/// itself.
+fn serve_redirects() -> String {
+    let listener =
+        TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}",
+        listener.local_addr().unwrap()
+    );
+    let base = url.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader =
+                BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap();
+            let (status, location, body) = match path {
+                "/start" => {
+                    ("302 Found", "/middle", String::new())
+                }
+                "/middle" => {
+                    ("302 Found", "/end", String::new())
+                }
+                "/loop" => {
+                    ("302 Found", "/loop", String::new())
+                }
+                "/end" => ("200 OK", "", String::new()),
+                _ => (
+                    "200 OK",
+                    "",
+                    json!({"links": [
+                        format!("{base}/start"),
+                        format!("{base}/loop"),
+                    ]})
+                    .to_string(),
+                ),
+            };
+            let location = if location.is_empty() {
+                String::new()
+            } else {
+                format!("location: {location}\r\n")
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\n{location}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+fn check_links_request(
+    resolve_redirects: bool,
+) -> CheckLinksRequestBuilder {
+    let mut builder = CheckLinksRequestBuilder::default();
+    builder
+        .domain("private")
+        .inbox("qa")
+        .message_id("m-1")
+        .resolve_redirects(resolve_redirects)
+        .max_redirects(3);
+    builder
+}
+
+#[test]
+fn classifies_links() {
+    let response: FetchLinkResponse = serde_json::from_value(json!({
+        "links": [
+            "https://example.com/welcome",
+            "https://example.com/track?url=https%3A%2F%2Fexample.com%2Fdocs",
+            "https://links.example.com/abc123",
+            "https://example.com/preferences?action=Unsubscribe&id=1",
+            "mailto:support@example.com?subject=Help",
+            "exampleapp://open/verify?token=abc",
+            "not a link"
+        ]
+    }))
+    .unwrap();
+
+    let kinds: Vec<LinkKind> = response
+        .classified()
+        .into_iter()
+        .map(|(_, kind)| kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            LinkKind::Web,
+            LinkKind::TrackingRedirect,
+            LinkKind::TrackingRedirect,
+            LinkKind::Unsubscribe,
+            LinkKind::Mailto,
+            LinkKind::DeepLink,
+            LinkKind::Invalid,
+        ]
+    );
+    assert!(kinds[0].is_http());
+    assert!(!kinds[4].is_http());
+}
+
+#[test]
+fn picks_single_matching_link() {
+    let email: Email = serde_json::from_value(json!({
+        "parts": [
+            {
+                "headers": {"content-type": "text/html"},
+                "body": "<a href=\"https://app.example.com/confirm?token=t0k3n&amp;user=42\">Confirm</a> <a href='https://app.example.com/help'>Help</a>"
+            },
+            {
+                "headers": {"content-type": "text/plain"},
+                "body": "Confirm: https://app.example.com/confirm?token=t0k3n&user=42.\nOr reset: https://app.example.com/reset?token=other"
+            }
+        ]
+    }))
+    .unwrap();
+
+    let link = email
+        .find_link(
+            &LinkPattern::default()
+                .host("APP.example.com")
+                .path_prefix("/confirm")
+                .require_query("token"),
+        )
+        .unwrap();
+    assert_eq!(link.param("token"), Some("t0k3n"));
+    assert_eq!(link.param("user"), Some("42"));
+
+    let err = email
+        .find_link(
+            &LinkPattern::default().require_query("token"),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::AmbiguousLink { ref matches, .. } if matches.len() == 2)
+    );
+
+    let err = email
+        .find_link(
+            &LinkPattern::default().path_prefix("/magic"),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::NoMatchingLink { ref links, .. } if links.len() == 3)
+    );
+}
+
+#[test]
+fn decodes_entities_in_hrefs() {
+    let email: Email = serde_json::from_value(json!({
+        "parts": [{
+            "headers": {"content-type": "text/html"},
+            "body": "<a href=\"https:&#x2F;&#x2F;app.example.com&#X2F;confirm?a=1&#38;b=&quot;2&quot;&amp;c=3\">Confirm</a> <a href=\"https://app.example.com/?q=&bogus;&#xZZ;\">Keep</a>"
+        }]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        email.links(),
+        [
+            "https://app.example.com/confirm?a=1&b=\"2\"&c=3",
+            "https://app.example.com/?q=&bogus;&#xZZ;",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn follows_redirects_up_to_the_limit() {
+    let url = serve_redirects();
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+
+    let report = client
+        .check_links(
+            check_links_request(true).build().unwrap(),
+        )
+        .await
+        .unwrap();
+    let start = &report.links[0];
+    assert_eq!(start.status, Some(StatusCode::OK));
+    assert_eq!(
+        start.final_url.as_deref(),
+        Some(format!("{url}/end").as_str())
+    );
+    let looping = &report.links[1];
+    assert!(looping.is_broken());
+    assert_eq!(
+        looping.error.as_deref(),
+        Some("too many redirects (3)")
+    );
+}
+
+#[tokio::test]
+async fn reports_first_response_without_resolving() {
+    let url = serve_redirects();
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap();
+
+    let report = client
+        .check_links(
+            check_links_request(false).build().unwrap(),
+        )
+        .await
+        .unwrap();
+    for link in &report.links {
+        assert_eq!(link.status, Some(StatusCode::FOUND));
+        assert_eq!(
+            link.final_url.as_ref(),
+            Some(&link.url)
+        );
+        assert!(!link.is_broken());
+    }
+}
+
+#[tokio::test]
+async fn custom_client_follows_its_own_redirects() {
+    let url = serve_redirects();
+    let client = Mailinator::builder()
+        .api_url(&url)
+        .api_token("XXXXX-XXXXXX")
+        .client(reqwest::Client::new())
+        .build()
+        .unwrap();
+
+    let report = client
+        .check_links(
+            check_links_request(true).build().unwrap(),
+        )
+        .await
+        .unwrap();
+    let start = &report.links[0];
+    assert_eq!(start.status, Some(StatusCode::OK));
+    assert_eq!(
+        start.final_url.as_deref(),
+        Some(format!("{url}/end").as_str())
+    );
+}
+
+/// Accepts connections and never answers them.
+fn serve_silence() -> String {
+    let listener =
+        TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}",
+        listener.local_addr().unwrap()
+    );
+    thread::spawn(move || {
+        let mut held = Vec::new();
+        for stream in listener.incoming() {
+            held.push(stream.unwrap());
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn link_checks_use_the_client_timeout() {
+    let silent = serve_silence();
+    let (api_url, _) = serve_sequence(vec![json!({
+        "links": [format!("{silent}/hang")],
+    })
+    .to_string()]);
+    let timeout = Duration::from_millis(200);
+    let clients = [
+        Mailinator::builder()
+            .api_url(&api_url)
+            .api_token("XXXXX-XXXXXX")
+            .timeout(timeout)
+            .build()
+            .unwrap(),
+        Mailinator::builder()
+            .api_url(&api_url)
+            .api_token("XXXXX-XXXXXX")
+            .client(
+                reqwest::Client::builder()
+                    .timeout(timeout)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    ];
+
+    for client in clients {
+        let start = Instant::now();
+        let report = tokio::time::timeout(
+            Duration::from_secs(10),
+            client.check_links(
+                check_links_request(false)
+                    .verify(true)
+                    .build()
+                    .unwrap(),
+            ),
+        )
+        .await
+        .expect("link check hung")
+        .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(report.links[0].is_broken());
+        assert!(report.links[0].error.is_some());
+    }
+}
+
+#[test]
+fn only_app_schemes_are_deep_links() {
+    assert_eq!(
+        LinkKind::of("exampleapp://open"),
+        LinkKind::DeepLink
+    );
+    for other in [
+        "tel:+14155550123",
+        "javascript:alert(1)",
+        "data:text/html,hi",
+        "sms:+14155550123",
+        "file:///etc/passwd",
+    ] {
+        assert_eq!(
+            LinkKind::of(other),
+            LinkKind::Other,
+            "{other}"
+        );
+        assert!(!LinkKind::of(other).is_http());
+    }
+}
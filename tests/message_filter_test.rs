@@ -0,0 +1,158 @@
+use chrono::{TimeZone, Utc};
+use mailinator_rs::prelude::{MessageFilter, Msg};
+use regex::Regex;
+
+fn msg(subject: &str, from: &str, time: u64) -> Msg {
+    Msg {
+        subject: Some(subject.to_owned()),
+        domain: Some(String::from("example.com")),
+        from: Some(from.to_owned()),
+        id: Some(String::from("id-1")),
+        to: Some(String::from("testinbox")),
+        time: Some(time),
+        seconds_ago: None,
+    }
+}
+
+#[test]
+fn matches_every_condition() {
+    let since = Utc.timestamp_millis_opt(1_000).unwrap();
+    let filter = MessageFilter::default()
+        .subject_contains("confirm")
+        .from("NoReply@example.com")
+        .to("testinbox")
+        .newer_than(since);
+
+    assert!(filter.matches(&msg(
+        "Please Confirm your account",
+        "noreply@example.com",
+        2_000
+    )));
+    assert!(!filter.matches(&msg(
+        "Please Confirm your account",
+        "noreply@example.com",
+        500
+    )));
+    assert!(!filter.matches(&msg(
+        "Welcome",
+        "noreply@example.com",
+        2_000
+    )));
+}
+
+#[test]
+fn matches_regex_and_custom_predicate() {
+    let filter = MessageFilter::default()
+        .subject_matches(
+            Regex::new(r"^Order #\d+$").unwrap(),
+        )
+        .matching(|m| m.id.as_deref() == Some("id-1"));
+
+    assert!(filter.matches(&msg(
+        "Order #42",
+        "shop@example.com",
+        1
+    )));
+    assert!(!filter.matches(&msg(
+        "Order #x",
+        "shop@example.com",
+        1
+    )));
+}
+
+#[test]
+fn received_time_range() {
+    let at = msg("Hi", "a@example.com", 1_700_000_000_000);
+    assert_eq!(
+        at.received_at(),
+        Utc.timestamp_millis_opt(1_700_000_000_000)
+            .single()
+    );
+
+    let since = Utc
+        .timestamp_millis_opt(1_700_000_000_000)
+        .unwrap();
+    let until = Utc
+        .timestamp_millis_opt(1_700_000_000_001)
+        .unwrap();
+    assert!(at.received_within(Some(since), Some(until)));
+    assert!(!at.received_within(None, Some(since)));
+    assert!(at.received_within(None, None));
+
+    let filter = MessageFilter::default().older_than(until);
+    assert!(filter.matches(&at));
+    assert!(!filter.matches(&msg(
+        "Hi",
+        "a@example.com",
+        1_700_000_000_001
+    )));
+
+    let undated = Msg { time: None, ..at };
+    assert_eq!(undated.received_at(), None);
+    assert!(!undated.received_within(Some(since), None));
+}
+
+#[test]
+fn each_condition_alone() {
+    let at = msg(
+        "Reset your Password",
+        "Help@Example.com",
+        2_000,
+    );
+    let missing = Msg {
+        subject: None,
+        from: None,
+        to: None,
+        time: None,
+        ..at.clone()
+    };
+    let before = Utc.timestamp_millis_opt(1_000).unwrap();
+    let after = Utc.timestamp_millis_opt(3_000).unwrap();
+
+    let cases = [
+        (
+            MessageFilter::default()
+                .subject_contains("PASSWORD"),
+            MessageFilter::default()
+                .subject_contains("invoice"),
+        ),
+        (
+            MessageFilter::default().subject_matches(
+                Regex::new(r"^Reset").unwrap(),
+            ),
+            MessageFilter::default().subject_matches(
+                Regex::new(r"^Password").unwrap(),
+            ),
+        ),
+        (
+            MessageFilter::default().from("help@example"),
+            MessageFilter::default().from("sales@"),
+        ),
+        (
+            MessageFilter::default().to("TESTINBOX"),
+            MessageFilter::default().to("other"),
+        ),
+        (
+            MessageFilter::default().newer_than(before),
+            MessageFilter::default().newer_than(after),
+        ),
+        (
+            MessageFilter::default().older_than(after),
+            MessageFilter::default().older_than(before),
+        ),
+        (
+            MessageFilter::default()
+                .matching(|m| m.time == Some(2_000)),
+            MessageFilter::default()
+                .matching(|m| m.time.is_none()),
+        ),
+    ];
+    for (n, (hit, miss)) in cases.iter().enumerate() {
+        assert!(hit.matches(&at), "case {n}");
+        assert!(!miss.matches(&at), "case {n}");
+        if n < 6 {
+            assert!(!hit.matches(&missing), "case {n}");
+        }
+    }
+    assert!(MessageFilter::default().matches(&missing));
+}
@@ -0,0 +1,124 @@
+use mailinator_rs::prelude::{Email, RawMessage};
+use serde_json::json;
+
+fn email() -> Email {
+    serde_json::from_value(json!({
+        "subject": "Invoice",
+        "parts": [
+            {
+                "headers": {
+                    "content-type": "text/plain; charset=\"iso-8859-1\"",
+                    "content-transfer-encoding": "quoted-printable"
+                },
+                "body": "Ol=E1, your invoice is =\r\nattached."
+            },
+            {
+                "headers": {
+                    "Content-Type": "text/html; charset=utf-8",
+                    "Content-Transfer-Encoding": "base64"
+                },
+                "body": "PHA+SGVsbG88L3A+\r\n"
+            },
+            {
+                "headers": {
+                    "content-type": "image/png",
+                    "content-id": "<logo@example.com>",
+                    "content-transfer-encoding": "base64"
+                },
+                "body": "iVBORw=="
+            },
+            {
+                "headers": {
+                    "content-type": "application/pdf; name=\"ignored.pdf\"",
+                    "content-disposition": "attachment; filename=\"invoice; 42.pdf\"",
+                    "content-transfer-encoding": "base64"
+                },
+                "body": "JVBERi0="
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn decodes_text_and_html_bodies() {
+    let email = email();
+
+    let text = email.text_body().unwrap();
+    assert_eq!(text.text, "Olá, your invoice is attached.");
+    assert_eq!(text.charset.as_deref(), Some("iso-8859-1"));
+
+    let html = email.html_body().unwrap();
+    assert_eq!(html.part, 1);
+    assert_eq!(html.text, "<p>Hello</p>");
+}
+
+#[test]
+fn separates_inline_images_from_attachments() {
+    let email = email();
+
+    let images = email.inline_images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(
+        images[0].content_id.as_deref(),
+        Some("logo@example.com")
+    );
+    assert_eq!(images[0].data, b"\x89PNG");
+
+    let attachments = email.attachments();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(
+        attachments[0].filename.as_deref(),
+        Some("invoice; 42.pdf")
+    );
+    assert_eq!(attachments[0].mime_type, "application/pdf");
+    assert_eq!(attachments[0].data, b"%PDF-");
+}
+
+#[test]
+fn parses_raw_message_into_mime_tree() {
+    let raw = RawMessage {
+        data: b"Received: from a\r\nReceived: from b\r\n\tby c\r\nSubject: Hi\r\nContent-Type: multipart/alternative; boundary=\"b1\"\r\n\r\npreamble\r\n--b1\r\nContent-Type: text/plain\r\nContent-Transfer-Encoding: quoted-printable\r\n\r\nHello=20world\r\n--b1\r\nContent-Type: text/html\r\n\r\n<p>Hello</p>\r\n--b1--\r\nepilogue\r\n"
+            .to_vec(),
+    };
+
+    let tree = raw.to_mime_tree();
+    assert_eq!(tree.header("subject"), Some("Hi"));
+    assert_eq!(
+        tree.header_all("received"),
+        ["from a", "from b by c"]
+    );
+    assert!(tree.is_multipart());
+
+    let leaves = tree.leaves();
+    assert_eq!(leaves.len(), 2);
+    assert_eq!(leaves[0].decoded_text(), "Hello world");
+    assert_eq!(leaves[1].content_type().value, "text/html");
+    assert_eq!(leaves[1].body, b"<p>Hello</p>");
+}
+
+#[test]
+fn nested_boundary_sharing_a_prefix_is_not_a_delimiter() {
+    let raw = RawMessage {
+        data: b"Content-Type: multipart/mixed; boundary=abc\r\n\r\n--abc\r\nContent-Type: multipart/alternative; boundary=abcdef\r\n\r\n--abcdef\r\nContent-Type: text/plain\r\n\r\nHello\r\n--abcdef\r\nContent-Type: text/html\r\n\r\n<p>Hello</p>\r\n--abcdef--\r\n--abc \t\r\nContent-Type: application/pdf\r\n\r\n%PDF-\r\n--abc-- \r\n--abcxyz\r\nignored\r\n"
+            .to_vec(),
+    };
+
+    let tree = raw.to_mime_tree();
+    assert_eq!(tree.children.len(), 2);
+    assert_eq!(
+        tree.children[0].content_type().value,
+        "multipart/alternative"
+    );
+    assert_eq!(tree.children[0].children.len(), 2);
+
+    let leaves = tree.leaves();
+    assert_eq!(leaves.len(), 3);
+    assert_eq!(leaves[0].body, b"Hello");
+    assert_eq!(leaves[1].body, b"<p>Hello</p>");
+    assert_eq!(
+        leaves[2].content_type().value,
+        "application/pdf"
+    );
+    assert_eq!(leaves[2].body, b"%PDF-");
+}
@@ -0,0 +1,171 @@
+use mailinator_rs::prelude::{
+    CreateRuleRequestBuilder, DeleteMessageRequestBuilder,
+    DomainName, DomainRef, DomainRequestBuilder, Error,
+    FetchMessageRequestBuilder, InboxName,
+    InjectMessageRequestBuilder, ListRulesRequestBuilder,
+    MessageId, RuleRequestBuilder,
+};
+
+#[test]
+fn normalizes_domain_names() {
+    let domain: DomainName =
+        " Team.Example.COM ".parse().unwrap();
+    assert_eq!(domain.as_str(), "team.example.com");
+    assert!(!domain.is_private());
+
+    assert!(DomainName::try_from("Private")
+        .unwrap()
+        .is_private());
+    assert!(DomainName::try_from("public")
+        .unwrap()
+        .is_public());
+
+    for bad in [
+        "",
+        "localhost",
+        "example..com",
+        "-example.com",
+        "exa_mple.com",
+        "qa@example.com",
+    ] {
+        assert!(
+            DomainName::try_from(bad).is_err(),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn validates_inbox_names_and_message_ids() {
+    assert_eq!(
+        InboxName::try_from("QA.Signup+42")
+            .unwrap()
+            .as_str(),
+        "qa.signup+42"
+    );
+    for bad in
+        ["", "qa*", "a,b", "qa inbox", "qa@example.com"]
+    {
+        assert!(InboxName::try_from(bad).is_err(), "{bad}");
+    }
+
+    let id = MessageId::try_from("Inbox-1700-ABC").unwrap();
+    assert_eq!(id.to_string(), "Inbox-1700-ABC");
+    assert!(MessageId::try_from("a/b").is_err());
+    assert!(MessageId::try_from("x".repeat(257)).is_err());
+}
+
+#[test]
+fn splits_full_addresses() {
+    let address = "Jane Doe <Jane.Doe+Signup@Example.com>";
+    assert_eq!(
+        InboxName::from_address(address).unwrap().as_str(),
+        "jane.doe+signup"
+    );
+    assert_eq!(
+        DomainName::from_address(address).unwrap().as_str(),
+        "example.com"
+    );
+    assert!(InboxName::from_address("jane.doe").is_err());
+}
+
+#[test]
+fn builders_validate_names() {
+    let domain = DomainName::try_from("private").unwrap();
+    assert!(FetchMessageRequestBuilder::default()
+        .domain(domain.clone())
+        .inbox(None)
+        .message_id("inbox-1700-abc")
+        .build()
+        .is_ok());
+    assert!(DeleteMessageRequestBuilder::default()
+        .domain(domain)
+        .inbox(InboxName::try_from("qa").unwrap())
+        .message_id(String::from("inbox-1700-abc"))
+        .build()
+        .is_ok());
+
+    let err = InjectMessageRequestBuilder::default()
+        .domain("example.com")
+        .inbox("qa@example.com")
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Builder(msg) if msg.contains("inbox name")),
+        "{err}"
+    );
+
+    let err = FetchMessageRequestBuilder::default()
+        .domain("example.com")
+        .message_id("id")
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("inbox"), "{err}");
+}
+
+#[test]
+fn domain_and_rule_builders_validate_domains() {
+    assert!(DomainRequestBuilder::default()
+        .domain("Team.Example.com")
+        .build()
+        .is_ok());
+    assert!(CreateRuleRequestBuilder::default()
+        .domain(DomainName::try_from("private").unwrap())
+        .build()
+        .is_ok());
+
+    for bad in
+        ["", "qa@example.com", "exa mple.com", "a/b", "ex*"]
+    {
+        assert!(
+            DomainRequestBuilder::default()
+                .domain(bad)
+                .build()
+                .is_err(),
+            "{bad:?}"
+        );
+        assert!(
+            CreateRuleRequestBuilder::default()
+                .domain(bad)
+                .build()
+                .is_err(),
+            "{bad:?}"
+        );
+        assert!(
+            ListRulesRequestBuilder::default()
+                .domain_id(bad)
+                .build()
+                .is_err(),
+            "{bad:?}"
+        );
+    }
+    assert!(DomainRequestBuilder::default()
+        .build()
+        .is_err());
+    assert!(RuleRequestBuilder::default()
+        .rule_id(String::from("r-1"))
+        .build()
+        .is_err());
+}
+
+#[test]
+fn domain_refs_take_names_or_opaque_ids() {
+    assert_eq!(
+        DomainRef::try_from(" Team.Example.COM ").unwrap(),
+        DomainRef::Name(
+            DomainName::try_from("team.example.com")
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        DomainRef::try_from("private").unwrap().as_str(),
+        "private"
+    );
+    for id in ["example", "5f8D2a1c77e0", "dom_1-A"] {
+        assert_eq!(
+            DomainRef::try_from(id).unwrap(),
+            DomainRef::Id(id.to_owned()),
+            "{id:?}"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use mailinator_rs::prelude::{
+    CodeExtractor, CodeSource, Email,
+};
+use regex::Regex;
+use serde_json::json;
+
+fn email(subject: &str, parts: serde_json::Value) -> Email {
+    serde_json::from_value(json!({
+        "subject": subject,
+        "from": "noreply@example.com",
+        "parts": parts,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn finds_code_next_to_keyword() {
+    let email = email(
+        "Welcome to Example 2024",
+        json!([{
+            "headers": {"content-type": "text/plain; charset=utf-8"},
+            "body": "Order 1234 shipped.\nYour verification code is 482913."
+        }]),
+    );
+
+    let codes = email.verification_codes();
+    assert_eq!(codes[0].code, "482913");
+    assert_eq!(codes[0].source, CodeSource::Text(0));
+    assert_eq!(
+        email.verification_code().as_deref(),
+        Some("482913")
+    );
+}
+
+#[test]
+fn strips_html_before_searching() {
+    let email = email(
+        "Sign in",
+        json!([{
+            "headers": {"content-type": "text/html"},
+            "body": "<style>.x{color:#123456}</style><p>Your one-time code:&nbsp;<b>A7K9Q2</b></p>"
+        }]),
+    );
+
+    let codes = email.verification_codes();
+    assert_eq!(codes[0].code, "A7K9Q2");
+    assert_eq!(codes[0].source, CodeSource::Html(0));
+}
+
+#[test]
+fn custom_patterns_rank_first() {
+    let email =
+        email("ACME-551100 is your code 123456", json!([]));
+
+    let codes = CodeExtractor::default()
+        .with_pattern(Regex::new(r"ACME-(\d{6})").unwrap())
+        .extract(&email);
+    assert_eq!(codes[0].code, "551100");
+    assert_eq!(codes[0].source, CodeSource::Subject);
+    assert_eq!(codes[1].code, "123456");
+}
+
+#[test]
+fn handles_non_ascii_text_before_code() {
+    let codes = CodeExtractor::default()
+        .extract_text("KOD İÇİN – 482913", CodeSource::Sms);
+    assert_eq!(codes[0].code, "482913");
+
+    let codes = CodeExtractor::default().extract_text(
+        "İİİİİİİİİİİİİİİİİİİİİİİİİİİİİİİİ code – 482913",
+        CodeSource::Sms,
+    );
+    assert_eq!(codes[0].code, "482913");
+    assert_eq!(codes[0].score, 15);
+}
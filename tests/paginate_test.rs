@@ -0,0 +1,90 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{inbox, serve_sequence};
+use mailinator_rs::prelude::{
+    Mailinator, PaginateInboxRequestBuilder,
+};
+
+fn client(url: &str) -> Mailinator {
+    Mailinator::builder()
+        .api_url(url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap()
+}
+
+fn request() -> PaginateInboxRequestBuilder {
+    let mut builder =
+        PaginateInboxRequestBuilder::default();
+    builder
+        .domain("private")
+        .inbox("qa")
+        .page_size(2_usize);
+    builder
+}
+
+#[test]
+fn rejects_empty_pages() {
+    assert!(request().page_size(0_usize).build().is_err());
+    assert!(request().build().is_ok());
+}
+
+#[tokio::test]
+async fn walks_pages_until_a_short_one() {
+    let (url, targets) = serve_sequence(vec![
+        inbox(&[("d", 4), ("c", 3)]),
+        // `c` shifted onto the second page.
+        inbox(&[("c", 3), ("b", 2)]),
+        inbox(&[("a", 1)]),
+    ]);
+
+    let msgs = client(&url)
+        .paginate_inbox(request().build().unwrap())
+        .collect_all()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = msgs
+        .iter()
+        .filter_map(|m| m.id.as_deref())
+        .collect();
+    assert_eq!(ids, ["d", "c", "b", "a"]);
+    let targets = targets.lock().unwrap().clone();
+    assert_eq!(targets.len(), 3);
+    for (target, skip) in targets.iter().zip([0, 2, 4]) {
+        assert!(
+            target.contains(&format!("skip={skip}")),
+            "{target}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn stops_once_past_the_range() {
+    let (url, targets) = serve_sequence(vec![
+        inbox(&[("d", 4), ("c", 3)]),
+        inbox(&[("b", 2), ("a", 1)]),
+        inbox(&[("z", 0), ("y", 0)]),
+    ]);
+
+    let msgs = client(&url)
+        .paginate_inbox(
+            request()
+                .since(Some(
+                    Utc.timestamp_millis_opt(3).unwrap(),
+                ))
+                .build()
+                .unwrap(),
+        )
+        .collect_all()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = msgs
+        .iter()
+        .filter_map(|m| m.id.as_deref())
+        .collect();
+    assert_eq!(ids, ["d", "c"]);
+    assert_eq!(targets.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{inbox, serve_sequence};
+use mailinator_rs::prelude::{
+    ApiMessageEndpoints, FetchInboxRequestBuilder,
+    Mailinator, RateLimit,
+};
+use std::{num::NonZeroU32, time::Duration};
+use tokio::time::Instant;
+
+fn client(limit: RateLimit) -> Mailinator {
+    let (url, _) = serve_sequence(vec![inbox(&[])]);
+    Mailinator::new(
+        Some(url),
+        Some(String::from("XXXXX-XXXXXX")),
+    )
+    .with_rate_limit(limit)
+}
+
+async fn fetch(client: &Mailinator) {
+    let request = FetchInboxRequestBuilder::default()
+        .domain(String::from("private"))
+        .inbox(String::from("qa"))
+        .query_params(None)
+        .build()
+        .unwrap();
+    client.fetch_inbox(request).await.unwrap();
+}
+
+fn limit(requests: u32) -> NonZeroU32 {
+    NonZeroU32::new(requests).unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn burst_passes_then_refills_at_the_rate() {
+    let client = client(
+        RateLimit::per_second(limit(2))
+            .with_burst(limit(2)),
+    );
+    let start = Instant::now();
+
+    fetch(&client).await;
+    fetch(&client).await;
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    assert_eq!(client.throttled_time(), Duration::ZERO);
+
+    for n in 1..=4 {
+        fetch(&client).await;
+        assert_eq!(
+            start.elapsed(),
+            Duration::from_millis(500 * n)
+        );
+    }
+    assert_eq!(
+        client.throttled_time(),
+        Duration::from_secs(2)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_time_refills_up_to_the_burst() {
+    let client = client(
+        RateLimit::per_minute(limit(60))
+            .with_burst(limit(3)),
+    );
+
+    for _ in 0..3 {
+        fetch(&client).await;
+    }
+    tokio::time::sleep(Duration::from_secs(60)).await;
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        fetch(&client).await;
+    }
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    fetch(&client).await;
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+    assert_eq!(
+        client.throttled_time(),
+        Duration::from_secs(1)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn clones_share_the_bucket() {
+    let client = client(RateLimit::per_second(limit(1)));
+    let other = client.clone();
+    let start = Instant::now();
+
+    fetch(&client).await;
+    fetch(&other).await;
+    fetch(&client).await;
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+    assert_eq!(
+        other.throttled_time(),
+        Duration::from_secs(2)
+    );
+}
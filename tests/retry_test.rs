@@ -0,0 +1,153 @@
+mod common;
+
+use common::{inbox, serve_replies, Reply};
+use mailinator_rs::prelude::{
+    ApiMessageEndpoints, Error, FetchInboxRequestBuilder,
+    FetchInboxResponse, Mailinator, RetryPolicyBuilder,
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+async fn fetch(
+    replies: Vec<Reply>,
+    policy: &mut RetryPolicyBuilder,
+) -> (Result<FetchInboxResponse, Error>, usize, Duration) {
+    let (url, targets) = serve_replies(replies);
+    let client = Mailinator::new(
+        Some(url),
+        Some(String::from("XXXXX-XXXXXX")),
+    )
+    .with_retry_policy(
+        policy.jitter(false).build().unwrap(),
+    );
+    let request = FetchInboxRequestBuilder::default()
+        .domain(String::from("private"))
+        .inbox(String::from("qa"))
+        .query_params(None)
+        .build()
+        .unwrap();
+
+    let start = Instant::now();
+    let result = client.fetch_inbox(request).await;
+    let elapsed = start.elapsed();
+    let requests = targets.lock().unwrap().len();
+    (result, requests, elapsed)
+}
+
+#[tokio::test(start_paused = true)]
+async fn delays_grow_exponentially_up_to_the_cap() {
+    let (result, requests, elapsed) = fetch(
+        vec![
+            Reply::status(503),
+            Reply::status(502),
+            Reply::status(500),
+            Reply::status(504),
+            Reply::ok(inbox(&[("a", 1)])),
+        ],
+        RetryPolicyBuilder::default()
+            .max_attempts(5)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(3)),
+    )
+    .await;
+
+    assert_eq!(result.unwrap().msgs.len(), 1);
+    assert_eq!(requests, 5);
+    // 1s, 2s, then 4s and 8s capped at 3s.
+    assert_eq!(elapsed, Duration::from_secs(9));
+}
+
+#[tokio::test(start_paused = true)]
+async fn gives_up_after_max_attempts() {
+    let (result, requests, elapsed) = fetch(
+        vec![Reply::status(503)],
+        RetryPolicyBuilder::default()
+            .max_attempts(3)
+            .base_delay(Duration::from_secs(1)),
+    )
+    .await;
+
+    assert!(matches!(result, Err(Error::Http(_))));
+    assert_eq!(requests, 3);
+    assert_eq!(elapsed, Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_after_takes_precedence() {
+    let (result, requests, elapsed) = fetch(
+        vec![
+            Reply::status(429).header("retry-after", "5"),
+            Reply::status(429).header("retry-after", "60"),
+            Reply::ok(inbox(&[])),
+        ],
+        RetryPolicyBuilder::default()
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(10)),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(requests, 3);
+    // 5s as asked, then 60s capped at 10s.
+    assert_eq!(elapsed, Duration::from_secs(15));
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_after_is_ignored_when_disabled() {
+    let (result, requests, elapsed) = fetch(
+        vec![
+            Reply::status(429).header("retry-after", "5"),
+            Reply::ok(inbox(&[])),
+        ],
+        RetryPolicyBuilder::default()
+            .base_delay(Duration::from_secs(1))
+            .respect_retry_after(false),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(requests, 2);
+    assert_eq!(elapsed, Duration::from_secs(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn non_retryable_statuses_fail_at_once() {
+    for (status, unauthorized) in
+        [(400, false), (401, true), (404, false)]
+    {
+        let (result, requests, elapsed) = fetch(
+            vec![
+                Reply::status(status),
+                Reply::ok(inbox(&[])),
+            ],
+            &mut RetryPolicyBuilder::default(),
+        )
+        .await;
+
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.status().map(|s| s.as_u16()),
+            Some(status)
+        );
+        assert_eq!(
+            matches!(err, Error::Unauthorized(_)),
+            unauthorized
+        );
+        assert_eq!(requests, 1, "{status}");
+        assert_eq!(elapsed, Duration::ZERO);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_statuses_are_configurable() {
+    let (result, requests, _) = fetch(
+        vec![Reply::status(503), Reply::ok(inbox(&[]))],
+        RetryPolicyBuilder::default().retry_statuses(vec![
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+        ]),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(requests, 1);
+}
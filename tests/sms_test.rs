@@ -0,0 +1,154 @@
+use mailinator_rs::prelude::{
+    Email, SmsMessage, SmsNumber,
+};
+use serde_json::json;
+
+#[test]
+fn parses_e164_numbers() {
+    let number: SmsNumber =
+        "+1 (415) 555-0123".parse().unwrap();
+    assert_eq!(number.to_string(), "+14155550123");
+    assert_eq!(number.digits(), "14155550123");
+    assert_eq!(
+        SmsNumber::try_from("14155550123").unwrap(),
+        number
+    );
+
+    assert!("0014155550123".parse::<SmsNumber>().is_err());
+    assert!("+1415555012345678"
+        .parse::<SmsNumber>()
+        .is_err());
+    assert!("+1 415 CALL NOW"
+        .parse::<SmsNumber>()
+        .is_err());
+}
+
+#[test]
+fn rejects_malformed_numbers() {
+    for input in [
+        "",
+        "+",
+        "0",
+        "+0123456789",
+        "00 44 20 7946 0018",
+        "7",
+        "+7",
+        "1234567890123456",
+        "+44 20 7946 0018 9999",
+        "+44 20 ABC 0018",
+        "+44_20_7946_0018",
+        "44207946OO18",
+        "++442079460018",
+    ] {
+        assert!(
+            input.parse::<SmsNumber>().is_err(),
+            "{input:?}"
+        );
+    }
+
+    assert_eq!(
+        "12".parse::<SmsNumber>().unwrap().digits(),
+        "12"
+    );
+    assert_eq!(
+        "123456789012345"
+            .parse::<SmsNumber>()
+            .unwrap()
+            .digits(),
+        "123456789012345"
+    );
+}
+
+fn sms_from(from: &str) -> SmsMessage {
+    let email: Email = serde_json::from_value(json!({
+        "from": from,
+        "parts": [],
+    }))
+    .unwrap();
+    let to: SmsNumber = "+14155550123".parse().unwrap();
+
+    SmsMessage::from_email(email, &to)
+}
+
+#[test]
+fn from_number_skips_short_codes() {
+    for short in
+        ["32665", "+32665", "123456", "12", "GOOGLE"]
+    {
+        assert_eq!(
+            sms_from(short).from_number(),
+            None,
+            "{short}"
+        );
+    }
+    assert_eq!(
+        sms_from("1234567")
+            .from_number()
+            .map(|n| n.to_string())
+            .as_deref(),
+        Some("+1234567")
+    );
+    assert_eq!(
+        sms_from("+44 7700 900123")
+            .from_number()
+            .map(|n| n.to_string())
+            .as_deref(),
+        Some("+447700900123")
+    );
+}
+
+#[test]
+fn reads_sms_from_fetched_message() {
+    let email: Email = serde_json::from_value(json!({
+        "from": "+447700900123",
+        "to": "14155550123",
+        "subject": "Your ACME code",
+        "id": "sms-1",
+        "time": 1_700_000_000_000_u64,
+        "parts": [{
+            "headers": {"content-type": "text/plain"},
+            "body": "Your ACME verification code is 730112. Valid 10 min.\n"
+        }],
+    }))
+    .unwrap();
+    let fallback: SmsNumber =
+        "+10000000000".parse().unwrap();
+
+    let sms = SmsMessage::from_email(email, &fallback);
+    assert_eq!(sms.to.to_string(), "+14155550123");
+    assert_eq!(
+        sms.text,
+        "Your ACME verification code is 730112. Valid 10 min."
+    );
+    assert_eq!(
+        sms.from_number().map(|n| n.to_string()).as_deref(),
+        Some("+447700900123")
+    );
+    assert_eq!(
+        sms.received_at.map(|t| t.timestamp()),
+        Some(1_700_000_000)
+    );
+    assert_eq!(
+        sms.verification_code().as_deref(),
+        Some("730112")
+    );
+}
+
+#[test]
+fn short_code_sender_is_not_a_number() {
+    let email: Email = serde_json::from_value(json!({
+        "from": "32665",
+        "subject": "G-482913 is your Google verification code.",
+        "parts": [],
+    }))
+    .unwrap();
+    let to: SmsNumber = "+14155550123".parse().unwrap();
+
+    let sms = SmsMessage::from_email(email, &to);
+    assert_eq!(sms.to, to);
+    assert_eq!(sms.from_number(), None);
+    assert_eq!(
+        sms.verification_code().as_deref(),
+        Some("482913")
+    );
+}
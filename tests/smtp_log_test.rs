@@ -0,0 +1,38 @@
+use mailinator_rs::prelude::{
+    SmtpEventKind, SmtpLogResponse,
+};
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn reads_smtp_transaction() {
+    let log: SmtpLogResponse = serde_json::from_value(json!({
+        "log": [
+            {"log": "Connection from 203.0.113.7", "time": "2024-03-01T10:00:00Z"},
+            {"log": "EHLO mail.example.com", "time": "2024-03-01T10:00:00.250Z"},
+            {"log": "MAIL FROM:<noreply@example.com> SIZE=1024", "time": 1709287200500_i64},
+            {"log": "RCPT TO:<qa@mailinator.com>"},
+            {"log": "DATA", "time": "2024-03-01T10:00:01Z"}
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        log.connecting_ip().as_deref(),
+        Some("203.0.113.7")
+    );
+    assert_eq!(
+        log.helo().as_deref(),
+        Some("mail.example.com")
+    );
+    assert_eq!(
+        log.mail_from().as_deref(),
+        Some("noreply@example.com")
+    );
+    assert_eq!(log.rcpt_to(), ["qa@mailinator.com"]);
+    assert_eq!(log.log[4].kind(), SmtpEventKind::Data);
+    assert_eq!(
+        log.duration(),
+        Some(Duration::from_secs(1))
+    );
+}
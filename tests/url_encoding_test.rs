@@ -0,0 +1,110 @@
+use mailinator_rs::prelude::{
+    ApiMessageEndpoints, FetchAttachmentRequestBuilder,
+    FetchInboxRequestBuilder,
+    FetchInboxRequestQueryParamsBuilder, InboxName,
+    InboxSelector, LookupField, Mailinator,
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+/// Answers one request with `body` and hands back its request
+/// target.
+fn serve_once(
+    body: &'static str,
+) -> (String, thread::JoinHandle<String>) {
+    let listener =
+        TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}",
+        listener.local_addr().unwrap()
+    );
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader =
+            BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap()
+            .to_owned()
+    });
+    (url, handle)
+}
+
+fn client(url: &str) -> Mailinator {
+    Mailinator::builder()
+        .api_url(url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn encodes_attachment_names_and_inboxes() {
+    let (url, server) = serve_once("%PDF");
+    let file = client(&url)
+        .fetch_attachment(
+            FetchAttachmentRequestBuilder::default()
+                .domain(String::from("private"))
+                .inbox(String::from("team+qa"))
+                .message_id(String::from("m-1"))
+                .attachment(LookupField::Name(
+                    String::from("report #1/2.pdf"),
+                ))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(file.data, b"%PDF");
+    assert_eq!(
+        server.join().unwrap(),
+        "/api/v2/domains/private/inboxes/team%2Bqa/messages/m-1/attachments/report%20%231%2F2.pdf"
+    );
+}
+
+#[tokio::test]
+async fn keeps_inbox_selector_syntax() {
+    let (url, server) = serve_once(
+        r#"{"domain":"private","to":"qa-*","msgs":[]}"#,
+    );
+    client(&url)
+        .fetch_inbox(
+            FetchInboxRequestBuilder::default()
+                .domain(String::from("private"))
+                .inbox(InboxSelector::Prefix(
+                    InboxName::try_from("qa+").unwrap(),
+                ))
+                .query_params(Some(
+                    FetchInboxRequestQueryParamsBuilder::default()
+                        .limit(Some(10))
+                        .build()
+                        .unwrap(),
+                ))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.join().unwrap(),
+        "/api/v2/domains/private/inboxes/qa%2B*?limit=10"
+    );
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{inbox, serve_sequence};
+use futures::StreamExt;
+use mailinator_rs::prelude::{
+    InboxWatch, Mailinator, WatchInboxRequestBuilder,
+};
+use std::time::Duration;
+
+fn client(url: &str) -> Mailinator {
+    Mailinator::builder()
+        .api_url(url)
+        .api_token("XXXXX-XXXXXX")
+        .build()
+        .unwrap()
+}
+
+async fn next_id(watch: &mut InboxWatch) -> String {
+    watch.next().await.unwrap().unwrap().id.unwrap()
+}
+
+#[test]
+fn rejects_empty_pages() {
+    assert!(WatchInboxRequestBuilder::default()
+        .domain("private")
+        .page_size(0_usize)
+        .build()
+        .is_err());
+}
+
+#[tokio::test(start_paused = true)]
+async fn yields_only_new_messages_once() {
+    let (url, targets) = serve_sequence(vec![
+        // Priming poll, recorded but not yielded.
+        inbox(&[("b", 2), ("a", 1)]),
+        inbox(&[("c", 3), ("b", 2), ("a", 1)]),
+        // A full page of new messages, the next page holds known
+        // ones and `d` again after shifting.
+        inbox(&[("f", 6), ("e", 5), ("d", 4)]),
+        inbox(&[("d", 4), ("c", 3), ("b", 2)]),
+        inbox(&[("f", 6), ("e", 5), ("d", 4)]),
+    ]);
+    let mut watch = client(&url).watch_inbox(
+        WatchInboxRequestBuilder::default()
+            .domain("private")
+            .inbox("qa")
+            .page_size(3_usize)
+            .build()
+            .unwrap(),
+    );
+
+    let ids = [
+        next_id(&mut watch).await,
+        next_id(&mut watch).await,
+        next_id(&mut watch).await,
+        next_id(&mut watch).await,
+    ];
+    assert_eq!(ids, ["c", "d", "e", "f"]);
+
+    let targets = targets.lock().unwrap().clone();
+    assert_eq!(targets.len(), 4);
+    assert!(
+        targets[0].contains("skip=0"),
+        "{}",
+        targets[0]
+    );
+    assert!(
+        targets[3].contains("skip=3"),
+        "{}",
+        targets[3]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn yields_existing_messages_when_asked() {
+    let (url, _) =
+        serve_sequence(vec![inbox(&[("b", 2), ("a", 1)])]);
+    let mut watch = client(&url).watch_inbox(
+        WatchInboxRequestBuilder::default()
+            .domain("private")
+            .include_existing(true)
+            .build()
+            .unwrap(),
+    );
+
+    assert_eq!(next_id(&mut watch).await, "a");
+    assert_eq!(next_id(&mut watch).await, "b");
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_paging_at_the_newest_time_once_seen_messages_expired(
+) {
+    let (url, targets) = serve_sequence(vec![
+        // Priming poll of a full first page, `b` and `a` lie on
+        // the second page and stay unlisted.
+        inbox(&[("d", 4), ("c", 3)]),
+        // `d` and `c` expired, older messages move up.
+        inbox(&[("e", 5), ("b", 2)]),
+        inbox(&[("a", 1)]),
+        inbox(&[("e", 5), ("b", 2)]),
+    ]);
+    let mut watch = client(&url).watch_inbox(
+        WatchInboxRequestBuilder::default()
+            .domain("private")
+            .inbox("qa")
+            .page_size(2_usize)
+            .build()
+            .unwrap(),
+    );
+
+    assert_eq!(next_id(&mut watch).await, "e");
+    assert!(tokio::time::timeout(
+        Duration::from_secs(60),
+        watch.next()
+    )
+    .await
+    .is_err());
+
+    let targets = targets.lock().unwrap().clone();
+    assert!(targets.len() > 2);
+    for target in targets {
+        assert!(target.contains("skip=0"), "{target}");
+    }
+}